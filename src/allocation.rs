@@ -0,0 +1,106 @@
+//! Construction of `AllocationInfo` records for binary export scenarios
+
+use memscope_rs::core::types::AllocationInfo;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+const ALLOCATION_KINDS: [(&str, &str, &str, usize); 6] = [
+    ("request_buffer", "Vec<u8>", "http::handler", 4096),
+    ("user_cache", "HashMap<String, User>", "cache::store", 65536),
+    ("shared_state", "Arc<Mutex<Vec<u64>>>", "app::state", 1024),
+    ("frame_data", "Vec<u32>", "render::frame", 262144),
+    (
+        "task_handle",
+        "Box<dyn Future<Output = ()>>",
+        "runtime::spawn",
+        256,
+    ),
+    ("scratch_string", "String", "util::format", 128),
+];
+
+/// Allocation timestamp of every synthetic record, in nanoseconds
+pub const PLACEHOLDER_TIMESTAMP: u64 = 1234567890;
+
+/// `AllocationInfo` timestamps are in nanoseconds, lifetimes in milliseconds
+pub const NANOS_PER_MS: u64 = 1_000_000;
+
+/// Deallocation timestamp of a record allocated at `timestamp_alloc` (ns)
+/// that lived `lifetime_ms`
+pub fn dealloc_timestamp(timestamp_alloc: u64, lifetime_ms: u64) -> u64 {
+    timestamp_alloc.saturating_add(lifetime_ms.saturating_mul(NANOS_PER_MS))
+}
+
+/// A live allocation with identifiers set and every extension field empty
+pub fn allocation(
+    ptr: usize,
+    size: usize,
+    var_name: &str,
+    type_name: &str,
+    scope_name: &str,
+    thread_id: &str,
+) -> AllocationInfo {
+    AllocationInfo {
+        ptr,
+        size,
+        var_name: Some(var_name.to_string()),
+        type_name: Some(type_name.to_string()),
+        scope_name: Some(scope_name.to_string()),
+        timestamp_alloc: PLACEHOLDER_TIMESTAMP,
+        timestamp_dealloc: None,
+        thread_id: thread_id.to_string(),
+        borrow_count: 0,
+        stack_trace: None,
+        is_leaked: false,
+        lifetime_ms: None,
+        borrow_info: None,
+        clone_info: None,
+        ownership_history_available: false,
+        smart_pointer_info: None,
+        memory_layout: None,
+        generic_info: None,
+        dynamic_type_info: None,
+        runtime_state: None,
+        stack_allocation: None,
+        temporary_object: None,
+        fragmentation_analysis: None,
+        generic_instantiation: None,
+        type_relationships: None,
+        type_usage: None,
+        function_call_tracking: None,
+        lifecycle_tracking: None,
+        access_tracking: None,
+        drop_chain_analysis: None,
+    }
+}
+
+/// `count` seeded allocations spread round-robin over `threads`, with about
+/// a quarter already deallocated
+pub fn synthetic_allocations(
+    count: usize,
+    threads: usize,
+    rng: &mut ChaCha8Rng,
+) -> Vec<AllocationInfo> {
+    let threads = threads.max(1);
+    (0..count)
+        .map(|idx| {
+            let (kind, type_name, scope_name, nominal_size) =
+                ALLOCATION_KINDS[idx % ALLOCATION_KINDS.len()];
+            let mut info = allocation(
+                0x1000_0000 + idx * 0x40,
+                rng.random_range(nominal_size / 2..=nominal_size * 2),
+                &format!("{}_{}", kind, idx),
+                type_name,
+                scope_name,
+                &format!("worker_{}", idx % threads + 1),
+            );
+            info.timestamp_alloc += idx as u64 * 1000;
+            info.borrow_count = rng.random_range(0..8);
+            if rng.random_range(0..4) == 0 {
+                let lifetime_ms = rng.random_range(1..=5000);
+                info.timestamp_dealloc = Some(dealloc_timestamp(info.timestamp_alloc, lifetime_ms));
+                info.lifetime_ms = Some(lifetime_ms);
+            }
+            info
+        })
+        .collect()
+}
@@ -0,0 +1,1030 @@
+//! Builders for the optional `AllocationInfo` extension fields
+//!
+//! Every builder derives its values from the allocation it describes (pointer,
+//! size, identifiers, type and timestamps), so the records stay internally
+//! consistent: a drop chain ends when the allocation is freed, an access range
+//! covers exactly its bytes, a layout adds up to its size. `populate` fills
+//! all fifteen extensions at once; type-specific ones (smart pointer, trait
+//! object, generics, temporaries) are only set where they apply.
+
+use memscope_rs::core::types::{
+    AddressRange, AllocationInfo, AllocatorStateInfo, BandwidthUtilization, BorrowState,
+    BranchPredictionImpact, CacheImpact, CachePerformanceInfo, CallPattern, CallPatternType,
+    ChildTypeInfo, CleanupAction, CleanupActionType, CodeBloatLevel, ComposedTypeInfo,
+    CompositionType, ConstraintType, ContextPerformanceMetrics, ContextType, CpuUsageInfo,
+    CreationContext, DispatchOverhead, DropChainAnalysis, DropChainNode,
+    DropChainPerformanceMetrics, DropImplementationType, DropPerformanceCharacteristics,
+    DynamicTypeInfo, EnhancedFragmentationAnalysis, EventPerformanceMetrics, ExpressionType,
+    FieldLayoutInfo, FragmentationCause, FragmentationCauseType, FragmentationMetrics,
+    FragmentationSeverity, FunctionCallTrackingInfo, GenericConstraint, GenericInstantiationInfo,
+    GenericTypeInfo, HotPath, ImpactLevel, LayoutEfficiency, LifecycleEfficiencyMetrics,
+    LifecycleEvent, LifecycleEventType, LifecycleStageDurations, LocalityMetrics,
+    MemoryAccessEvent, MemoryAccessPattern, MemoryAccessPerformanceImpact, MemoryAccessStatistics,
+    MemoryAccessTrackingInfo, MemoryAccessType, MemoryLayoutInfo, MemoryLocationType,
+    MemoryPressureInfo, MemoryPressureLevel, MemoryState, MonomorphizationInfo,
+    ObjectLifecycleInfo, OptimizationPotential, OwnershipHierarchy, OwnershipNode, OwnershipType,
+    PaddingAnalysis, PaddingLocation, PaddingReason, ParentTypeInfo, PerformanceCharacteristics,
+    PerformanceImpact, PerformanceSnapshot, RefCountSnapshot, RelationshipType,
+    ResourceLeakAnalysis, ResourceWasteAssessment, RuntimeStateInfo, ScopeType, SmartPointerInfo,
+    SmartPointerType, SourceLocation, StackAllocationInfo, StackScopeInfo, TemporaryObjectInfo,
+    TemporaryUsagePattern, TypeErasureInfo, TypeParameter, TypePerformanceImpact,
+    TypeRelationshipInfo, TypeUsageInfo, UsageContext, UsageTimePoint, VTableInfo, VTableMethod,
+};
+
+use crate::allocation::{dealloc_timestamp, NANOS_PER_MS};
+use crate::type_tree::{Bound, GenericArg, TypePath, TypeTree};
+
+/// Size of a pointer-sized word on the target
+const WORD: usize = std::mem::size_of::<usize>();
+
+/// What is known about an allocation's type beyond its name
+#[derive(Debug, Clone, Default)]
+pub struct TypeShape {
+    /// Outermost type without parameters, e.g. `HashMap`
+    pub base_type: String,
+    /// Top-level generic arguments, e.g. `String` and `Arc<Mutex<CacheEntry>>`
+    pub type_parameters: Vec<String>,
+    /// Generic types the value is nested in, outermost first, e.g. `Arc`,
+    /// `Mutex` for `Arc<Mutex<T>>`
+    pub parent_types: Vec<String>,
+    /// Associated types bound anywhere in the type, e.g. `Output`
+    pub associated_types: Vec<String>,
+    /// Smart pointer holding the value, looking through an outer `Pin`
+    pub smart_pointer: Option<SmartPointerType>,
+    /// Standard collection the value is, which fixes its header fields
+    pub collection: Option<CollectionKind>,
+    /// Principal trait of the outermost `dyn` type, e.g. `Future` or
+    /// `std::error::Error`
+    pub trait_object: Option<String>,
+    /// `*const T` or `*mut T`
+    pub raw_pointer: bool,
+}
+
+/// Standard collections with a known header layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionKind {
+    /// `Vec<T>` and `String`
+    Vec,
+    /// `HashMap` and `HashSet`
+    HashTable,
+    /// `BTreeMap` and `BTreeSet`
+    BTree,
+}
+
+impl CollectionKind {
+    fn header_fields(self) -> &'static [&'static str] {
+        match self {
+            CollectionKind::Vec => &["ptr", "capacity", "len"],
+            CollectionKind::HashTable => &["ctrl", "bucket_mask", "items", "growth_left"],
+            CollectionKind::BTree => &["root", "height", "length"],
+        }
+    }
+}
+
+impl TypeShape {
+    /// Shape of a Rust type string, read from its parsed type tree
+    pub fn parse(type_name: &str) -> Result<TypeShape, String> {
+        let tree = TypeTree::parse(type_name)?;
+        Ok(TypeShape {
+            base_type: tree.base_type(),
+            type_parameters: tree.type_arguments(),
+            parent_types: tree.wrapper_chain(),
+            associated_types: tree.associated_type_names(),
+            smart_pointer: smart_pointer_type(&tree),
+            collection: collection_kind(&tree),
+            trait_object: trait_object_name(&tree),
+            raw_pointer: matches!(tree, TypeTree::RawPointer { .. }),
+        })
+    }
+}
+
+/// Path of `tree` when it is a plain path type
+fn type_path(tree: &TypeTree) -> Option<&TypePath> {
+    match tree {
+        TypeTree::Path(path) => Some(path),
+        _ => None,
+    }
+}
+
+/// Segment names of a path, e.g. `["std", "rc", "Weak"]`
+fn segment_names(path: &TypePath) -> Vec<&str> {
+    path.segments
+        .iter()
+        .map(|segment| segment.name.as_str())
+        .collect()
+}
+
+fn smart_pointer_type(tree: &TypeTree) -> Option<SmartPointerType> {
+    let mut path = type_path(tree)?;
+    if let [.., pin] = path.segments.as_slice() {
+        if pin.name == "Pin" {
+            let inner = pin.args.iter().find_map(|arg| match arg {
+                GenericArg::Type(inner) => Some(inner),
+                _ => None,
+            })?;
+            path = type_path(inner)?;
+        }
+    }
+    match segment_names(path).as_slice() {
+        [.., "Box"] => Some(SmartPointerType::Box),
+        [.., "Rc"] => Some(SmartPointerType::Rc),
+        [.., "Arc"] => Some(SmartPointerType::Arc),
+        [.., "rc", "Weak"] => Some(SmartPointerType::RcWeak),
+        // An unqualified `Weak` is taken to be `Arc`'s, the more common one
+        [.., "Weak"] => Some(SmartPointerType::ArcWeak),
+        _ => None,
+    }
+}
+
+fn collection_kind(tree: &TypeTree) -> Option<CollectionKind> {
+    match segment_names(type_path(tree)?).as_slice() {
+        [.., "Vec" | "String"] => Some(CollectionKind::Vec),
+        [.., "HashMap" | "HashSet"] => Some(CollectionKind::HashTable),
+        [.., "BTreeMap" | "BTreeSet"] => Some(CollectionKind::BTree),
+        _ => None,
+    }
+}
+
+fn trait_object_name(tree: &TypeTree) -> Option<String> {
+    let bounds = match tree.find(&|tree| matches!(tree, TypeTree::TraitObject(_)))? {
+        TypeTree::TraitObject(bounds) => bounds,
+        _ => return None,
+    };
+    bounds.iter().find_map(|bound| match bound {
+        Bound::Trait {
+            maybe: false, path, ..
+        } => Some(segment_names(path).join("::")),
+        _ => None,
+    })
+}
+
+/// Fill every extension field of `allocation` that applies to its type
+pub fn populate(allocation: &mut AllocationInfo, shape: &TypeShape) {
+    let type_name = allocation.type_name.clone().unwrap_or_default();
+    let var_name = allocation.var_name.clone().unwrap_or_default();
+    let scope_name = allocation.scope_name.clone().unwrap_or_default();
+
+    allocation.smart_pointer_info = smart_pointer_info(allocation, shape);
+    allocation.memory_layout = Some(memory_layout(&type_name, shape, allocation.size));
+    allocation.generic_info = generic_info(shape, allocation.size);
+    allocation.dynamic_type_info = dynamic_type_info(&type_name, shape, allocation.ptr);
+    allocation.runtime_state = Some(runtime_state(allocation.size));
+    allocation.stack_allocation = Some(stack_allocation(&var_name, &scope_name, allocation.ptr));
+    allocation.temporary_object = temporary_object(allocation);
+    allocation.fragmentation_analysis = Some(fragmentation_analysis(allocation.size));
+    allocation.generic_instantiation = generic_instantiation(shape, allocation);
+    allocation.type_relationships = Some(type_relationships(&type_name, shape));
+    allocation.type_usage = Some(type_usage(allocation));
+    allocation.function_call_tracking = Some(function_call_tracking(&scope_name, allocation.size));
+    allocation.lifecycle_tracking = Some(lifecycle_tracking(allocation));
+    allocation.access_tracking = Some(access_tracking(allocation));
+    allocation.drop_chain_analysis = Some(drop_chain_analysis(allocation, shape));
+}
+
+/// Reference counts for `Rc`/`Arc`/`Weak`, ownership for `Box`
+///
+/// Only what the record implies is filled in: the counts a shared pointer
+/// starts with, and the original it was cloned from. Later counts and the
+/// addresses of clones are not recorded anywhere, so they are left empty.
+pub fn smart_pointer_info(
+    allocation: &AllocationInfo,
+    shape: &TypeShape,
+) -> Option<SmartPointerInfo> {
+    let pointer_type = shape.smart_pointer.clone()?;
+
+    let weak = matches!(
+        pointer_type,
+        SmartPointerType::RcWeak | SmartPointerType::ArcWeak
+    );
+    let counted = !matches!(pointer_type, SmartPointerType::Box);
+
+    // A `Box` points at its value; `Rc`/`Arc` and their `Weak`s point at a
+    // heap block whose strong and weak counts precede the value
+    let data_ptr = if counted {
+        allocation.ptr + 2 * WORD
+    } else {
+        allocation.ptr
+    };
+
+    // Every `Rc`/`Arc` starts with one strong reference and no weak ones
+    let ref_count_history = if counted {
+        vec![RefCountSnapshot {
+            timestamp: allocation.timestamp_alloc,
+            strong_count: 1,
+            weak_count: 0,
+        }]
+    } else {
+        Vec::new()
+    };
+
+    Some(SmartPointerInfo {
+        data_ptr,
+        cloned_from: allocation
+            .clone_info
+            .as_ref()
+            .and_then(|clone| clone.original_ptr),
+        clones: Vec::new(),
+        ref_count_history,
+        weak_count: None,
+        is_weak_reference: weak,
+        is_data_owner: !weak,
+        is_implicitly_deallocated: allocation.timestamp_dealloc.is_some(),
+        pointer_type,
+    })
+}
+
+/// A header of pointer-sized fields followed by the payload
+pub fn memory_layout(type_name: &str, shape: &TypeShape, size: usize) -> MemoryLayoutInfo {
+    let header_fields = shape
+        .collection
+        .map_or(&["ptr"][..], CollectionKind::header_fields);
+    let header = header_fields.len() * WORD;
+    let payload = size.saturating_sub(header);
+    // Payloads are padded up to the next 16-byte boundary
+    let padding = (16 - payload % 16) % 16;
+
+    let mut field_layout: Vec<FieldLayoutInfo> = header_fields
+        .iter()
+        .enumerate()
+        .map(|(idx, name)| FieldLayoutInfo {
+            field_name: name.to_string(),
+            field_type: "usize".to_string(),
+            offset: idx * WORD,
+            size: WORD,
+            alignment: WORD,
+            is_padding: false,
+        })
+        .collect();
+    field_layout.push(FieldLayoutInfo {
+        field_name: "data".to_string(),
+        field_type: type_name.to_string(),
+        offset: header,
+        size: payload,
+        alignment: WORD,
+        is_padding: false,
+    });
+
+    let total_size = header + payload + padding;
+    let padding_locations = if padding > 0 {
+        vec![PaddingLocation {
+            start_offset: header + payload,
+            size: padding,
+            reason: PaddingReason::StructAlignment,
+        }]
+    } else {
+        Vec::new()
+    };
+
+    MemoryLayoutInfo {
+        total_size,
+        alignment: WORD,
+        field_layout,
+        padding_info: PaddingAnalysis {
+            total_padding_bytes: padding,
+            padding_locations,
+            padding_ratio: padding as f64 / total_size.max(1) as f64,
+            optimization_suggestions: Vec::new(),
+        },
+        layout_efficiency: LayoutEfficiency {
+            memory_utilization: (header + payload) as f64 / total_size.max(1) as f64,
+            cache_friendliness: if total_size <= 64 {
+                1.0
+            } else {
+                64.0 / total_size as f64
+            },
+            alignment_waste: padding,
+            optimization_potential: if padding == 0 {
+                OptimizationPotential::None
+            } else {
+                OptimizationPotential::Minor {
+                    potential_savings: padding,
+                }
+            },
+        },
+        container_analysis: None,
+    }
+}
+
+/// Type parameters and monomorphization cost of a generic type
+pub fn generic_info(shape: &TypeShape, size: usize) -> Option<GenericTypeInfo> {
+    if shape.type_parameters.is_empty() {
+        return None;
+    }
+    let instance_count = shape.type_parameters.len();
+
+    Some(GenericTypeInfo {
+        base_type: shape.base_type.clone(),
+        type_parameters: shape
+            .type_parameters
+            .iter()
+            .enumerate()
+            .map(|(idx, concrete)| TypeParameter {
+                name: ((b'T' + idx as u8) as char).to_string(),
+                concrete_type: concrete.clone(),
+                size: nominal_size(concrete),
+                alignment: WORD,
+                is_lifetime: concrete.starts_with('\''),
+            })
+            .collect(),
+        monomorphization_info: MonomorphizationInfo {
+            instance_count,
+            per_instance_memory: size / instance_count,
+            total_memory_usage: size,
+            code_bloat_assessment: match instance_count {
+                1 => CodeBloatLevel::Low,
+                2 => CodeBloatLevel::Moderate,
+                _ => CodeBloatLevel::High,
+            },
+        },
+        constraints: shape
+            .associated_types
+            .iter()
+            .map(|associated| GenericConstraint {
+                parameter_name: associated.clone(),
+                constraint_type: ConstraintType::Trait(shape.base_type.clone()),
+                description: format!("{}::{} is fixed by the type", shape.base_type, associated),
+            })
+            .collect(),
+    })
+}
+
+/// Vtable and dispatch cost of a `dyn Trait` object
+pub fn dynamic_type_info(
+    type_name: &str,
+    shape: &TypeShape,
+    ptr: usize,
+) -> Option<DynamicTypeInfo> {
+    let trait_name = shape.trait_object.clone()?;
+    // drop_in_place, size and align precede the trait's own methods
+    let methods = vec![
+        VTableMethod {
+            name: "drop_in_place".to_string(),
+            signature: "unsafe fn(*mut ())".to_string(),
+            vtable_offset: 0,
+        },
+        VTableMethod {
+            name: trait_method(&trait_name).to_string(),
+            signature: format!("fn(&mut self) /* {} */", trait_name),
+            vtable_offset: 3 * WORD,
+        },
+    ];
+
+    Some(DynamicTypeInfo {
+        trait_name: trait_name.clone(),
+        vtable_info: VTableInfo {
+            // vtables live in read-only data, 16-byte aligned
+            vtable_ptr: 0x5555_0000 | (ptr & 0xfff0),
+            vtable_size: (3 + methods.len() - 1) * WORD,
+            method_count: methods.len(),
+            methods,
+        },
+        concrete_type: None,
+        dispatch_overhead: DispatchOverhead {
+            indirect_call_overhead_ns: 2.5,
+            cache_miss_probability: 0.05,
+            branch_misprediction_rate: 0.02,
+            performance_impact: PerformanceImpact::Minor,
+        },
+        type_erasure_info: TypeErasureInfo {
+            type_info_recoverable: false,
+            size_known: false,
+            alignment_known: false,
+            debug_info: Some(type_name.to_string()),
+        },
+    })
+}
+
+/// Process state at allocation time, scaled by the allocation's size
+pub fn runtime_state(size: usize) -> RuntimeStateInfo {
+    let (heap_size, heap_used) = heap_usage(size);
+    let pressure = (size as f64 / (64.0 * 1024.0)).min(1.0);
+    RuntimeStateInfo {
+        cpu_usage: CpuUsageInfo {
+            current_usage_percent: 20.0 + 40.0 * pressure,
+            average_usage_percent: 25.0,
+            peak_usage_percent: 60.0 + 30.0 * pressure,
+            intensive_operations_count: size / 1024,
+        },
+        memory_pressure: MemoryPressureInfo {
+            pressure_level: match pressure {
+                p if p < 0.25 => MemoryPressureLevel::Low,
+                p if p < 0.5 => MemoryPressureLevel::Moderate,
+                p if p < 0.9 => MemoryPressureLevel::High,
+                _ => MemoryPressureLevel::Critical,
+            },
+            available_memory_percent: 100.0 * (1.0 - pressure * 0.8),
+            allocation_failures: 0,
+            fragmentation_level: pressure * 0.3,
+        },
+        cache_performance: CachePerformanceInfo {
+            l1_hit_rate: 0.95 - pressure * 0.1,
+            l2_hit_rate: 0.85 - pressure * 0.1,
+            l3_hit_rate: 0.70 - pressure * 0.1,
+            cache_miss_penalty_ns: 80.0,
+            access_pattern: if size >= 4096 {
+                MemoryAccessPattern::Sequential
+            } else {
+                MemoryAccessPattern::Random
+            },
+        },
+        allocator_state: AllocatorStateInfo {
+            allocator_type: "System".to_string(),
+            heap_size,
+            heap_used,
+            free_blocks_count: 128,
+            largest_free_block: 1 << 20,
+            efficiency_score: 0.9 - pressure * 0.2,
+        },
+        gc_info: None,
+    }
+}
+
+/// The stack slot holding the handle to the heap data
+pub fn stack_allocation(var_name: &str, scope_name: &str, ptr: usize) -> StackAllocationInfo {
+    StackAllocationInfo {
+        frame_id: ptr >> 12,
+        var_name: var_name.to_string(),
+        stack_offset: -((3 * WORD) as isize),
+        size: 3 * WORD,
+        function_name: scope_name.to_string(),
+        stack_depth: 3,
+        scope_info: StackScopeInfo {
+            scope_type: if scope_name.contains("async") || scope_name.contains("tokio") {
+                ScopeType::Async
+            } else if scope_name.contains("ffi") {
+                ScopeType::Unsafe
+            } else {
+                ScopeType::Function
+            },
+            start_line: Some(10),
+            end_line: Some(40),
+            parent_scope: None,
+            nesting_level: 1,
+        },
+    }
+}
+
+/// Short-lived buffers: named `temp_*`/`tmp_*` or freed within 100 ms
+pub fn temporary_object(allocation: &AllocationInfo) -> Option<TemporaryObjectInfo> {
+    let var_name = allocation.var_name.as_deref().unwrap_or_default();
+    let short_lived = allocation.lifetime_ms.is_some_and(|ms| ms < 100);
+    if !(var_name.starts_with("temp_") || var_name.starts_with("tmp_") || short_lived) {
+        return None;
+    }
+    let lifetime_ns = allocation.lifetime_ms.map(|ms| ms * NANOS_PER_MS);
+
+    Some(TemporaryObjectInfo {
+        temp_id: allocation.ptr,
+        created_at: allocation.timestamp_alloc,
+        destroyed_at: allocation.timestamp_dealloc,
+        lifetime_ns,
+        creation_context: CreationContext {
+            function_name: allocation.scope_name.clone().unwrap_or_default(),
+            expression_type: ExpressionType::FunctionCall,
+            source_location: Some(source_location(allocation)),
+            call_stack: allocation.stack_trace.clone().unwrap_or_default(),
+        },
+        usage_pattern: TemporaryUsagePattern::Immediate,
+        location_type: MemoryLocationType::Heap,
+        optimization_potential: OptimizationPotential::Minor {
+            potential_savings: allocation.size,
+        },
+        hot_path_involvement: allocation.borrow_count > 5,
+    })
+}
+
+/// Heap fragmentation around an allocation of `size` bytes
+pub fn fragmentation_analysis(size: usize) -> EnhancedFragmentationAnalysis {
+    let (total_heap_size, used_heap_size) = heap_usage(size);
+    let free_heap_size = total_heap_size - used_heap_size;
+    let free_block_count = 128;
+    let largest_free_block = 1 << 20;
+    let external = 1.0 - largest_free_block as f64 / free_heap_size as f64;
+
+    EnhancedFragmentationAnalysis {
+        total_heap_size,
+        used_heap_size,
+        free_heap_size,
+        free_block_count,
+        free_block_distribution: Vec::new(),
+        fragmentation_metrics: FragmentationMetrics {
+            external_fragmentation: external,
+            internal_fragmentation: ((16 - size % 16) % 16) as f64 / size.max(1) as f64,
+            largest_free_block,
+            average_free_block_size: free_heap_size as f64 / free_block_count as f64,
+            severity_level: if external > 0.8 {
+                FragmentationSeverity::High
+            } else {
+                FragmentationSeverity::Moderate
+            },
+        },
+        fragmentation_causes: vec![FragmentationCause {
+            cause_type: FragmentationCauseType::MixedAllocationSizes,
+            description: format!("{} byte block among mixed-size allocations", size),
+            impact_level: ImpactLevel::Medium,
+            mitigation_suggestion: "Pool allocations of similar size".to_string(),
+        }],
+    }
+}
+
+/// Where and how often a generic type is instantiated
+pub fn generic_instantiation(
+    shape: &TypeShape,
+    allocation: &AllocationInfo,
+) -> Option<GenericInstantiationInfo> {
+    if shape.type_parameters.is_empty() {
+        return None;
+    }
+    Some(GenericInstantiationInfo {
+        base_type: shape.base_type.clone(),
+        concrete_parameters: shape.type_parameters.clone(),
+        instantiation_location: source_location(allocation),
+        instantiation_count: 1,
+        memory_per_instance: allocation.size,
+        total_memory_usage: allocation.size,
+        performance_characteristics: PerformanceCharacteristics {
+            avg_allocation_time_ns: 50.0 + allocation.size as f64 / 64.0,
+            avg_deallocation_time_ns: 30.0,
+            access_pattern: MemoryAccessPattern::Sequential,
+            cache_impact: CacheImpact {
+                l1_impact_score: 0.9,
+                l2_impact_score: 0.8,
+                l3_impact_score: 0.7,
+                cache_line_efficiency: (allocation.size.min(64) as f64) / 64.0,
+            },
+            branch_prediction_impact: BranchPredictionImpact {
+                misprediction_rate: 0.02,
+                pipeline_stall_impact: 0.1,
+                predictability_score: 0.9,
+            },
+        },
+    })
+}
+
+/// Wrappers the type is held in and the types it is composed of
+pub fn type_relationships(type_name: &str, shape: &TypeShape) -> TypeRelationshipInfo {
+    let parent_types: Vec<ParentTypeInfo> = shape
+        .parent_types
+        .iter()
+        .enumerate()
+        .map(|(level, parent)| ParentTypeInfo {
+            type_name: parent.clone(),
+            relationship_type: RelationshipType::Composition,
+            inheritance_level: level as u32 + 1,
+        })
+        .collect();
+    let composed_types: Vec<ComposedTypeInfo> = shape
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(idx, parameter)| ComposedTypeInfo {
+            type_name: parameter.clone(),
+            field_name: format!("param_{}", idx),
+            composition_type: CompositionType::Generic,
+            memory_offset: None,
+        })
+        .collect();
+    let child_types = shape
+        .associated_types
+        .iter()
+        .map(|associated| ChildTypeInfo {
+            type_name: format!("<{} as {}>::{}", type_name, shape.base_type, associated),
+            relationship_type: RelationshipType::Association,
+            specialization_level: 1,
+            usage_frequency: 1,
+        })
+        .collect();
+
+    TypeRelationshipInfo {
+        type_name: type_name.to_string(),
+        complexity_score: (parent_types.len() + composed_types.len() + 1) as u32,
+        inheritance_depth: parent_types.len() as u32,
+        composition_breadth: composed_types.len() as u32,
+        parent_types,
+        child_types,
+        composed_types,
+    }
+}
+
+/// How often and where the type is used, from its borrow history
+pub fn type_usage(allocation: &AllocationInfo) -> TypeUsageInfo {
+    let type_name = allocation.type_name.clone().unwrap_or_default();
+    let scope_name = allocation.scope_name.clone().unwrap_or_default();
+    let uses = allocation.borrow_count as u64 + 1;
+
+    TypeUsageInfo {
+        type_name,
+        total_usage_count: uses,
+        usage_contexts: vec![UsageContext {
+            context_type: ContextType::LocalVariable,
+            location: scope_name.clone(),
+            frequency: uses as u32,
+            performance_metrics: ContextPerformanceMetrics {
+                avg_execution_time_ns: 120.0,
+                allocation_frequency: 1.0 / uses as f64,
+                cache_miss_rate: 0.05,
+                branch_misprediction_rate: 0.02,
+            },
+        }],
+        usage_timeline: vec![UsageTimePoint {
+            timestamp: allocation.timestamp_alloc,
+            usage_count: uses as u32,
+            memory_usage: allocation.size,
+            performance_snapshot: PerformanceSnapshot {
+                cpu_usage: 25.0,
+                memory_usage: allocation.size as f64,
+                cache_hit_rate: 0.9,
+                throughput: 1000.0,
+            },
+        }],
+        hot_paths: vec![HotPath {
+            path_id: format!("{}::hot", scope_name),
+            call_sequence: allocation.stack_trace.clone().unwrap_or_default(),
+            execution_frequency: uses,
+            total_execution_time_ns: uses * 120,
+            avg_execution_time_ns: 120.0,
+            memory_allocations: 1,
+            bottlenecks: Vec::new(),
+        }],
+        performance_impact: TypePerformanceImpact {
+            performance_score: 80.0,
+            memory_efficiency_score: 75.0,
+            cpu_efficiency_score: 85.0,
+            cache_efficiency_score: 90.0,
+            optimization_recommendations: Vec::new(),
+        },
+    }
+}
+
+/// Calls of the allocating function
+pub fn function_call_tracking(scope_name: &str, size: usize) -> FunctionCallTrackingInfo {
+    let call_count = 10;
+    let total_execution_time_ns = 1_000 + size as u64 * 2;
+    FunctionCallTrackingInfo {
+        function_name: scope_name.to_string(),
+        call_count,
+        total_execution_time_ns,
+        avg_execution_time_ns: total_execution_time_ns as f64 / call_count as f64,
+        allocations_per_call: 1.0,
+        memory_per_call: size as f64 / call_count as f64,
+        call_patterns: vec![CallPattern {
+            pattern_type: if scope_name.contains("async") || scope_name.contains("tokio") {
+                CallPatternType::Async
+            } else {
+                CallPatternType::Sequential
+            },
+            frequency: call_count,
+            avg_duration_ns: total_execution_time_ns as f64 / call_count as f64,
+            memory_impact: size as i64,
+        }],
+    }
+}
+
+/// Creation, use and (when freed) drop events with stage durations
+pub fn lifecycle_tracking(allocation: &AllocationInfo) -> ObjectLifecycleInfo {
+    let type_name = allocation.type_name.clone().unwrap_or_default();
+    let created = allocation.timestamp_alloc;
+    let first_use = created + 1;
+    let event = |event_type, timestamp, borrow_state| LifecycleEvent {
+        event_type,
+        timestamp,
+        location: source_location(allocation),
+        memory_state: MemoryState {
+            memory_location: MemoryLocationType::Heap,
+            memory_address: allocation.ptr,
+            object_size: allocation.size,
+            reference_count: None,
+            borrow_state,
+        },
+        performance_metrics: EventPerformanceMetrics {
+            cpu_cycles: 200,
+            memory_bandwidth_bytes: allocation.size,
+            cache_misses: 1,
+            processing_time_ns: 80,
+        },
+        call_stack: allocation.stack_trace.clone().unwrap_or_default(),
+    };
+
+    let mut lifecycle_events = vec![
+        event(
+            LifecycleEventType::Creation,
+            created,
+            BorrowState::NotBorrowed,
+        ),
+        event(
+            LifecycleEventType::FirstUse,
+            first_use,
+            BorrowState::SharedBorrow {
+                count: allocation.borrow_count.max(1) as u32,
+            },
+        ),
+    ];
+    if let Some(dropped) = allocation.timestamp_dealloc {
+        lifecycle_events.push(event(
+            LifecycleEventType::Drop,
+            dropped,
+            BorrowState::NotBorrowed,
+        ));
+    }
+    let total_lifetime_ns = allocation.lifetime_ms.map(|ms| ms * NANOS_PER_MS);
+
+    ObjectLifecycleInfo {
+        object_id: allocation.ptr,
+        type_name,
+        lifecycle_events,
+        total_lifetime_ns,
+        stage_durations: LifecycleStageDurations {
+            creation_to_first_use_ns: Some(first_use - created),
+            active_use_duration_ns: total_lifetime_ns,
+            last_use_to_destruction_ns: allocation.timestamp_dealloc.map(|_| 0),
+            borrowed_time_ns: allocation.borrow_count as u64 * 1_000,
+            idle_time_ns: 0,
+        },
+        efficiency_metrics: LifecycleEfficiencyMetrics {
+            utilization_ratio: if allocation.is_leaked { 0.0 } else { 0.9 },
+            memory_efficiency: 0.85,
+            performance_efficiency: 0.9,
+            resource_waste: ResourceWasteAssessment {
+                wasted_memory_percent: if allocation.is_leaked { 100.0 } else { 0.0 },
+                wasted_cpu_percent: 0.0,
+                premature_destructions: 0,
+                unused_instances: u32::from(allocation.is_leaked),
+                optimization_opportunities: Vec::new(),
+            },
+        },
+        lifecycle_patterns: Vec::new(),
+    }
+}
+
+/// Reads and writes over the allocation's address range, from its borrows
+pub fn access_tracking(allocation: &AllocationInfo) -> MemoryAccessTrackingInfo {
+    let (reads, writes) = allocation.borrow_info.as_ref().map_or((1, 1), |borrows| {
+        (
+            borrows.immutable_borrows as u64,
+            borrows.mutable_borrows as u64,
+        )
+    });
+    let function_name = allocation.scope_name.clone().unwrap_or_default();
+    let access = |access_type, offset: u64| MemoryAccessEvent {
+        access_type,
+        timestamp: allocation.timestamp_alloc + offset,
+        address: allocation.ptr,
+        size: allocation.size.min(64),
+        function_name: function_name.clone(),
+        source_location: Some(source_location(allocation)),
+        access_latency_ns: 5,
+        cache_hit: true,
+    };
+    let sequential = if allocation.size >= 4096 { 90.0 } else { 40.0 };
+
+    MemoryAccessTrackingInfo {
+        region_id: allocation.ptr,
+        address_range: AddressRange {
+            start_address: allocation.ptr,
+            end_address: allocation.ptr + allocation.size,
+            size: allocation.size,
+        },
+        access_events: vec![
+            access(MemoryAccessType::Write, 0),
+            access(MemoryAccessType::Read, 1),
+        ],
+        access_statistics: MemoryAccessStatistics {
+            total_reads: reads,
+            total_writes: writes,
+            read_write_ratio: reads as f64 / writes.max(1) as f64,
+            avg_access_frequency: (reads + writes) as f64,
+            peak_access_frequency: (reads + writes) as f64 * 1.5,
+            locality_metrics: LocalityMetrics {
+                temporal_locality: 0.8,
+                spatial_locality: sequential / 100.0,
+                sequential_access_percent: sequential,
+                random_access_percent: 100.0 - sequential,
+                stride_patterns: Vec::new(),
+            },
+            bandwidth_utilization: BandwidthUtilization {
+                peak_bandwidth: allocation.size as f64 * 2.0,
+                avg_bandwidth: allocation.size as f64,
+                efficiency_percent: 50.0,
+                bottlenecks: Vec::new(),
+            },
+        },
+        access_patterns: Vec::new(),
+        performance_impact: MemoryAccessPerformanceImpact {
+            performance_score: 85.0,
+            cache_efficiency_impact: 0.1,
+            memory_bus_utilization: 0.2,
+            optimization_recommendations: Vec::new(),
+        },
+    }
+}
+
+/// Drop of the allocation and of the values it owns, one level deep
+pub fn drop_chain_analysis(allocation: &AllocationInfo, shape: &TypeShape) -> DropChainAnalysis {
+    let type_name = allocation.type_name.clone().unwrap_or_default();
+    let dropped_at = allocation.timestamp_dealloc.unwrap_or_else(|| {
+        dealloc_timestamp(
+            allocation.timestamp_alloc,
+            allocation.lifetime_ms.unwrap_or(0),
+        )
+    });
+    let ownership_type = match shape.smart_pointer {
+        Some(SmartPointerType::Rc) => OwnershipType::SharedRc,
+        Some(SmartPointerType::Arc) => OwnershipType::SharedArc,
+        Some(SmartPointerType::RcWeak | SmartPointerType::ArcWeak) => OwnershipType::Weak,
+        _ if shape.raw_pointer => OwnershipType::Raw,
+        _ => OwnershipType::Unique,
+    };
+
+    // Generic types drop the values they hold; others only themselves
+    let is_generic = |type_name: &str| {
+        TypeTree::parse(type_name).is_ok_and(|tree| !tree.type_arguments().is_empty())
+    };
+    let node = |object_id, type_name: &str, children, drop_duration_ns: u64| DropChainNode {
+        object_id,
+        type_name: type_name.to_string(),
+        drop_timestamp: dropped_at,
+        drop_duration_ns,
+        children,
+        drop_impl_type: if is_generic(type_name) {
+            DropImplementationType::Collection
+        } else {
+            DropImplementationType::Automatic
+        },
+        cleanup_actions: vec![CleanupAction {
+            action_type: CleanupActionType::MemoryDeallocation,
+            timestamp: dropped_at,
+            resource_description: format!("{} bytes at {:#x}", allocation.size, object_id),
+            success: !allocation.is_leaked,
+        }],
+        performance_characteristics: DropPerformanceCharacteristics {
+            execution_time_ns: drop_duration_ns,
+            cpu_usage_percent: 1.0,
+            memory_operations: 1,
+            io_operations: 0,
+            system_calls: 0,
+            impact_level: ImpactLevel::Low,
+        },
+    };
+    let children: Vec<DropChainNode> = shape
+        .type_parameters
+        .iter()
+        .enumerate()
+        .map(|(idx, parameter)| node(allocation.ptr + (idx + 1) * WORD, parameter, Vec::new(), 20))
+        .collect();
+    let drop_sequence = children.clone();
+    let total_objects = children.len() + 1;
+    let child_time: u64 = children.iter().map(|child| child.drop_duration_ns).sum();
+    let root_object = node(allocation.ptr, &type_name, children, 50 + child_time);
+    let total_drop_time_ns = root_object.drop_duration_ns;
+
+    DropChainAnalysis {
+        drop_sequence,
+        total_drop_time_ns,
+        performance_metrics: DropChainPerformanceMetrics {
+            total_objects,
+            max_depth: if total_objects > 1 { 2 } else { 1 },
+            avg_drop_time_ns: total_drop_time_ns as f64 / total_objects as f64,
+            slowest_drop_ns: total_drop_time_ns,
+            efficiency_score: 0.9,
+            bottlenecks: Vec::new(),
+        },
+        ownership_hierarchy: OwnershipHierarchy {
+            root_owners: vec![OwnershipNode {
+                object_id: allocation.ptr,
+                type_name: type_name.clone(),
+                ownership_type,
+                owned_objects: Vec::new(),
+                reference_count: allocation
+                    .clone_info
+                    .as_ref()
+                    .map(|clone| clone.clone_count + 1),
+                weak_reference_count: None,
+            }],
+            max_depth: 1,
+            total_objects,
+            transfer_events: Vec::new(),
+            weak_references: Vec::new(),
+            circular_references: Vec::new(),
+        },
+        leak_detection: ResourceLeakAnalysis {
+            potential_leaks: Vec::new(),
+            detection_confidence: if allocation.is_leaked { 0.9 } else { 0.1 },
+            usage_patterns: Vec::new(),
+            prevention_recommendations: Vec::new(),
+        },
+        root_object,
+    }
+}
+
+/// Total and used heap bytes once an allocation of `size` bytes is live
+fn heap_usage(size: usize) -> (usize, usize) {
+    let used = (8 << 20) + size;
+    ((16 << 20).max(used * 2), used)
+}
+
+/// Location derived from the allocation's innermost frame
+fn source_location(allocation: &AllocationInfo) -> SourceLocation {
+    let scope = allocation.scope_name.as_deref().unwrap_or("main");
+    SourceLocation {
+        file: format!("src/{}.rs", scope.replace("::", "/")),
+        line: 10 + (allocation.ptr >> 8) as u32 % 200,
+        column: 9,
+    }
+}
+
+/// Stack size of a type argument, in bytes
+fn nominal_size(type_name: &str) -> usize {
+    let tree = TypeTree::parse(type_name).ok();
+    let name = tree
+        .as_ref()
+        .and_then(type_path)
+        .and_then(|path| path.segments.last())
+        .map(|segment| segment.name.as_str());
+    match name {
+        Some("u8" | "i8" | "bool") => 1,
+        Some("u16" | "i16") => 2,
+        Some("u32" | "i32" | "f32" | "char") => 4,
+        Some("u64" | "i64" | "f64" | "usize" | "isize") => 8,
+        Some("String" | "Vec") => 3 * WORD,
+        Some("Arc" | "Rc" | "Box") => WORD,
+        _ => 2 * WORD,
+    }
+}
+
+/// The method a trait object is typically called through
+fn trait_method(trait_name: &str) -> &'static str {
+    let trait_name = trait_name.rsplit("::").next().unwrap_or(trait_name);
+    match trait_name {
+        "Future" => "poll",
+        "Iterator" => "next",
+        "Fn" | "FnMut" | "FnOnce" => "call",
+        "Error" => "source",
+        _ => "handle",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(type_name: &str) -> TypeShape {
+        TypeShape::parse(type_name).unwrap_or_else(|e| panic!("{type_name}: {e}"))
+    }
+
+    #[test]
+    fn smart_pointers_are_recognised_by_path() {
+        assert!(matches!(
+            shape("std::sync::Arc<T>").smart_pointer,
+            Some(SmartPointerType::Arc)
+        ));
+        assert!(matches!(
+            shape("Rc<RefCell<Node>>").smart_pointer,
+            Some(SmartPointerType::Rc)
+        ));
+        assert!(matches!(
+            shape("std::rc::Weak<Node>").smart_pointer,
+            Some(SmartPointerType::RcWeak)
+        ));
+        assert!(matches!(
+            shape("Weak<Node>").smart_pointer,
+            Some(SmartPointerType::ArcWeak)
+        ));
+        assert!(matches!(
+            shape("Pin<Box<dyn Future<Output = ()>>>").smart_pointer,
+            Some(SmartPointerType::Box)
+        ));
+        assert!(shape("Vec<Arc<T>>").smart_pointer.is_none());
+    }
+
+    #[test]
+    fn collections_use_the_outermost_type() {
+        assert_eq!(shape("String").collection, Some(CollectionKind::Vec));
+        assert_eq!(
+            shape("std::collections::HashMap<String, Arc<Mutex<CacheEntry>>>").collection,
+            Some(CollectionKind::HashTable)
+        );
+        assert_eq!(
+            shape("BTreeMap<UserId, Vec<Message<JsonValue>>>").collection,
+            Some(CollectionKind::BTree)
+        );
+        assert_eq!(shape("Arc<Mutex<HashMap<K, V>>>").collection, None);
+    }
+
+    #[test]
+    fn trait_objects_are_found_inside_wrappers() {
+        assert_eq!(
+            shape("Pin<Box<dyn Future<Output = ()>>>")
+                .trait_object
+                .as_deref(),
+            Some("Future")
+        );
+        assert_eq!(
+            shape("Box<dyn std::error::Error + Send + Sync>")
+                .trait_object
+                .as_deref(),
+            Some("std::error::Error")
+        );
+        assert_eq!(shape("Vec<u8>").trait_object, None);
+        assert!(shape("*const u8").raw_pointer);
+    }
+}
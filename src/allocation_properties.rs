@@ -0,0 +1,182 @@
+//! Property-Based Export and Parse Invariants for AllocationInfo
+//!
+//! This scenario generates arbitrary valid `AllocationInfo` sets with proptest
+//! (extreme sizes, empty/huge/non-ASCII strings, missing options, deallocation
+//! before allocation, duplicate pointers) and checks two properties:
+//! 1. `export_to_binary` followed by `BinaryReader::read_all` returns every
+//!    record unchanged and in order
+//! 2. `parse_binary_to_html_direct` never panics on an exported file
+//!
+//! Failing inputs are shrunk to a minimal case, which is printed with its
+//! per-field mismatches and saved as JSON next to the seed.
+
+use memscope_rs::core::types::AllocationInfo;
+use memscope_rs::export::binary;
+use memscope_stress_test::allocation_strategy::arb_allocations;
+use memscope_stress_test::{cli, report_roundtrip, seed};
+use proptest::test_runner::{Config, RngAlgorithm, TestCaseError, TestError, TestRng, TestRunner};
+use rand::Rng;
+use tempfile::TempDir;
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Cases per property unless `--cases` says otherwise
+const DEFAULT_CASES: u32 = 64;
+
+/// Most allocations in one generated set
+const MAX_ALLOCATIONS: usize = 24;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🎰 Property-Based Export and Parse Invariants for AllocationInfo");
+    println!("================================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    let cases = match cli::flag_value("--cases") {
+        Some(value) => value
+            .parse()
+            .map_err(|e| format!("Invalid --cases value '{}': {}", value, e))?,
+        None => DEFAULT_CASES,
+    };
+
+    let output_dir = PathBuf::from("./Memoryanalysis/allocation_properties");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+    println!(
+        "   🧪 {} cases per property, up to {} allocations each",
+        cases, MAX_ALLOCATIONS
+    );
+
+    // Panics are the subject of the test; report them instead of printing
+    // a backtrace for every shrinking step
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let parse_errors = AtomicUsize::new(0);
+    let results = [
+        (
+            "lossless_round_trip",
+            runner(seed, 0, cases).run(&arb_allocations(MAX_ALLOCATIONS), |allocations| {
+                check_round_trip(&allocations).map_err(TestCaseError::fail)
+            }),
+        ),
+        (
+            "html_never_panics",
+            runner(seed, 1, cases).run(&arb_allocations(MAX_ALLOCATIONS), |allocations| {
+                check_html_conversion(&allocations, &parse_errors).map_err(TestCaseError::fail)
+            }),
+        ),
+    ];
+    panic::set_hook(default_hook);
+
+    let mut failed = 0;
+    for (property, result) in results {
+        match result {
+            Ok(()) => println!("   ✅ {}: held for {} cases", property, cases),
+            Err(TestError::Abort(reason)) => {
+                failed += 1;
+                println!("   ❌ {}: aborted: {}", property, reason);
+            }
+            Err(TestError::Fail(reason, minimal)) => {
+                failed += 1;
+                let case_path = output_dir.join(format!("{}_minimal_case.json", property));
+                std::fs::write(&case_path, serde_json::to_string_pretty(&minimal)?)?;
+                println!(
+                    "   ❌ {}: {} (minimal case of {} allocation(s) saved to {})",
+                    property,
+                    reason,
+                    minimal.len(),
+                    case_path.display()
+                );
+            }
+        }
+    }
+    println!(
+        "   ℹ️  parse_binary_to_html_direct returned an error (without panicking) {} time(s)",
+        parse_errors.load(Ordering::Relaxed)
+    );
+
+    seed::announce(seed);
+    if failed == 0 {
+        println!("   ✅ SUCCESS: export is lossless and HTML conversion never panics");
+        Ok(())
+    } else {
+        println!("   ❌ FAILED: {} of 2 properties violated", failed);
+        Err(format!("{} properties violated", failed).into())
+    }
+}
+
+/// A runner whose case stream is fixed by `seed` and `stream`
+fn runner(seed: u64, stream: usize, cases: u32) -> TestRunner {
+    let key: [u8; 32] = seed::stream_rng(seed, stream).random();
+    TestRunner::new_with_rng(
+        Config {
+            cases,
+            failure_persistence: None,
+            ..Config::default()
+        },
+        TestRng::from_seed(RngAlgorithm::ChaCha, &key),
+    )
+}
+
+/// Export, read back, and compare every record with the one at the same index
+fn check_round_trip(allocations: &[AllocationInfo]) -> Result<(), String> {
+    let temp_dir = TempDir::new().map_err(|e| e.to_string())?;
+    let binary_path = temp_dir.path().join("roundtrip.memscope");
+
+    let read_back = catch_panic("export_to_binary", || {
+        binary::export_to_binary(allocations, &binary_path)
+            .map_err(|e| format!("export_to_binary rejected valid input: {}", e))?;
+        binary::BinaryReader::new(&binary_path)
+            .and_then(|mut reader| reader.read_all())
+            .map_err(|e| format!("BinaryReader failed on its own export: {}", e))
+    })?;
+
+    let mismatches = report_roundtrip::diff_read_back(allocations, &read_back);
+    if let Some(first) = mismatches.first() {
+        return Err(format!("{} ({} field(s) differ)", first, mismatches.len()));
+    }
+    Ok(())
+}
+
+/// Convert an exported file to HTML; errors are counted, panics fail
+fn check_html_conversion(
+    allocations: &[AllocationInfo],
+    parse_errors: &AtomicUsize,
+) -> Result<(), String> {
+    let temp_dir = TempDir::new().map_err(|e| e.to_string())?;
+    let binary_path = temp_dir.path().join("arbitrary.memscope");
+    let html_path = temp_dir.path().join("arbitrary.html");
+
+    if binary::export_to_binary(allocations, &binary_path).is_err() {
+        // Export failures are the round-trip property's concern
+        return Ok(());
+    }
+    let converted = catch_panic("parse_binary_to_html_direct", || {
+        Ok(binary::parse_binary_to_html_direct(
+            &binary_path,
+            &html_path,
+            "Arbitrary Allocations",
+        ))
+    })?;
+    if converted.is_err() {
+        parse_errors.fetch_add(1, Ordering::Relaxed);
+    }
+    Ok(())
+}
+
+/// Run `operation`, turning a panic into an error naming `what`
+fn catch_panic<T>(what: &str, operation: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    panic::catch_unwind(AssertUnwindSafe(operation)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        Err(format!("{} panicked: {}", what, message))
+    })
+}
@@ -0,0 +1,190 @@
+//! Property-based generation of `AllocationInfo` records
+//!
+//! The strategies cover what hand-written demos never do: zero and
+//! `usize::MAX` sizes, empty, non-ASCII and 64 KiB strings, missing options,
+//! deallocation before allocation and pointers shared by several records.
+//! Extension fields are filled through `allocation_extensions` for records
+//! whose numbers are plausible enough for the builders' arithmetic.
+
+use memscope_rs::core::types::{AllocationInfo, BorrowInfo, CloneInfo};
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+
+use crate::allocation_extensions::{self, TypeShape};
+
+/// Largest string a generated identifier can hold
+pub const MAX_STRING_LEN: usize = 64 * 1024;
+
+/// Arbitrary identifier: empty, Rust-like, any printable Unicode, or huge
+pub fn arb_string() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(String::new()),
+        "[a-zA-Z_][a-zA-Z0-9_:<>, ]{0,40}",
+        "\\PC{0,64}",
+        (1..=MAX_STRING_LEN).prop_map(|len| "x".repeat(len)),
+    ]
+}
+
+/// Pointers drawn from a handful of slots, so records collide, or anywhere
+pub fn arb_ptr() -> impl Strategy<Value = usize> {
+    prop_oneof![
+        (0x10usize..0x14).prop_map(|slot| slot * 0x100),
+        any::<usize>(),
+    ]
+}
+
+/// Sizes including the extremes
+pub fn arb_size() -> impl Strategy<Value = usize> {
+    prop_oneof![
+        Just(0usize),
+        Just(1usize),
+        Just(usize::MAX),
+        1usize..1 << 20,
+        any::<usize>(),
+    ]
+}
+
+pub fn arb_borrow_info() -> impl Strategy<Value = BorrowInfo> {
+    (
+        any::<usize>(),
+        any::<usize>(),
+        any::<usize>(),
+        option::of(any::<u64>()),
+    )
+        .prop_map(
+            |(
+                immutable_borrows,
+                mutable_borrows,
+                max_concurrent_borrows,
+                last_borrow_timestamp,
+            )| {
+                BorrowInfo {
+                    immutable_borrows,
+                    mutable_borrows,
+                    max_concurrent_borrows,
+                    last_borrow_timestamp,
+                }
+            },
+        )
+}
+
+pub fn arb_clone_info() -> impl Strategy<Value = CloneInfo> {
+    (any::<usize>(), any::<bool>(), option::of(arb_ptr())).prop_map(
+        |(clone_count, is_clone, original_ptr)| CloneInfo {
+            clone_count,
+            is_clone,
+            original_ptr,
+        },
+    )
+}
+
+/// A single allocation; timestamps are independent, so deallocation may
+/// precede allocation
+pub fn arb_allocation() -> impl Strategy<Value = AllocationInfo> {
+    let identity = (
+        arb_ptr(),
+        arb_size(),
+        option::of(arb_string()),
+        option::of(arb_string()),
+        option::of(arb_string()),
+        arb_string(),
+    );
+    let timing = (
+        any::<u64>(),
+        option::of(any::<u64>()),
+        option::of(any::<u64>()),
+        any::<bool>(),
+    );
+    let ownership = (
+        any::<usize>(),
+        option::of(vec(arb_string(), 0..8)),
+        option::of(arb_borrow_info()),
+        option::of(arb_clone_info()),
+        any::<bool>(),
+        any::<bool>(),
+    );
+
+    (identity, timing, ownership).prop_map(
+        |(
+            (ptr, size, var_name, type_name, scope_name, thread_id),
+            (timestamp_alloc, timestamp_dealloc, lifetime_ms, is_leaked),
+            (
+                borrow_count,
+                stack_trace,
+                borrow_info,
+                clone_info,
+                ownership_history_available,
+                with_extensions,
+            ),
+        )| {
+            let mut allocation = AllocationInfo {
+                ptr,
+                size,
+                var_name,
+                type_name,
+                scope_name,
+                timestamp_alloc,
+                timestamp_dealloc,
+                thread_id,
+                borrow_count,
+                stack_trace,
+                is_leaked,
+                lifetime_ms,
+                borrow_info,
+                clone_info,
+                ownership_history_available,
+                smart_pointer_info: None,
+                memory_layout: None,
+                generic_info: None,
+                dynamic_type_info: None,
+                runtime_state: None,
+                stack_allocation: None,
+                temporary_object: None,
+                fragmentation_analysis: None,
+                generic_instantiation: None,
+                type_relationships: None,
+                type_usage: None,
+                function_call_tracking: None,
+                lifecycle_tracking: None,
+                access_tracking: None,
+                drop_chain_analysis: None,
+            };
+            if with_extensions && plausible(&allocation) {
+                // Arbitrary names rarely parse as types; those keep an empty shape
+                let shape = allocation
+                    .type_name
+                    .as_deref()
+                    .and_then(|name| TypeShape::parse(name).ok())
+                    .unwrap_or_default();
+                allocation_extensions::populate(&mut allocation, &shape);
+            }
+            allocation
+        },
+    )
+}
+
+/// Between zero and `max_len` allocations
+pub fn arb_allocations(max_len: usize) -> impl Strategy<Value = Vec<AllocationInfo>> {
+    vec(arb_allocation(), 0..=max_len)
+}
+
+/// Whether the extension builders can derive values without overflowing
+fn plausible(allocation: &AllocationInfo) -> bool {
+    let small = |value: u64, bits: u32| value < 1 << bits;
+    small(allocation.ptr as u64, 47)
+        && small(allocation.size as u64, 40)
+        && small(allocation.timestamp_alloc, 62)
+        && allocation
+            .timestamp_dealloc
+            .is_none_or(|dealloc| small(dealloc, 62))
+        && allocation.lifetime_ms.is_none_or(|ms| small(ms, 32))
+        && allocation.borrow_count < 1 << 20
+        && allocation
+            .clone_info
+            .as_ref()
+            .is_none_or(|clone| clone.clone_count < 1 << 10)
+        && allocation.borrow_info.as_ref().is_none_or(|borrows| {
+            borrows.immutable_borrows < 1 << 20 && borrows.mutable_borrows < 1 << 20
+        })
+}
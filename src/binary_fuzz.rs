@@ -0,0 +1,376 @@
+//! Fuzzing parse_binary_to_html_direct with Malformed .memscope Files
+//!
+//! Every case starts from the checked-in
+//! `Memoryanalysis/comprehensive_demo.memscope` and stacks one to three
+//! structure-aware mutations (magic, version, record count, header fields,
+//! string table, record tags and lengths, string lengths, truncation, dropped
+//! or duplicated records, byte flips). Each case is parsed in a child process,
+//! so aborts and runaway allocations cannot take the harness down. A case
+//! fails when:
+//! 1. the parser panics or the child dies from a signal (e.g. an aborted
+//!    allocation)
+//! 2. the child's peak virtual memory grows more than `--memory-budget-mb`
+//!    (default 256) over parsing the unmutated file
+//! 3. no result arrives within `--case-timeout-ms` (default 5000)
+//! 4. a file with a corrupted magic, or a truncated one, is accepted
+//!
+//! Failing inputs are kept under `crashes/`; replay one with `--replay <file>`.
+
+use memscope_rs::export::binary;
+use memscope_stress_test::binary_mutation::{self, Mutation};
+use memscope_stress_test::{cli, process_memory, seed};
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Write as _;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Well-formed file every case is mutated from
+const SEED_CORPUS: &str = "./Memoryanalysis/comprehensive_demo.memscope";
+
+/// Flag overriding the number of mutated files
+const CASES_FLAG: &str = "--cases";
+const DEFAULT_CASES: u64 = 500;
+
+/// Flag overriding how far a case may grow peak virtual memory, in MB
+const MEMORY_BUDGET_FLAG: &str = "--memory-budget-mb";
+const DEFAULT_MEMORY_BUDGET_MB: u64 = 256;
+
+/// Flag overriding how long one case may run, in milliseconds
+const TIMEOUT_FLAG: &str = "--case-timeout-ms";
+const DEFAULT_TIMEOUT_MS: u64 = 5000;
+
+/// Parse one file and exit; used for child processes and by hand
+const REPLAY_FLAG: &str = "--replay";
+
+/// Most mutations stacked on one case
+const MAX_STACKED: usize = 3;
+
+/// Child exit codes for a parse that returned `Ok` or `Err`
+const ACCEPTED_EXIT: i32 = 0;
+const REJECTED_EXIT: i32 = 3;
+/// Exit code of a Rust process whose main thread panicked
+const PANIC_EXIT: i32 = 101;
+
+/// Prefix of the line a child prints with its own peak virtual memory
+const PEAK_PREFIX: &str = "peak_virtual_bytes:";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// How a child process finished
+enum Outcome {
+    Accepted,
+    Rejected,
+    Panicked,
+    Died(String),
+    OverBudget,
+    Hung,
+}
+
+/// Per-mutation tallies for the report
+#[derive(Default)]
+struct Tally {
+    cases: usize,
+    accepted: usize,
+    rejected: usize,
+    failed: usize,
+}
+
+/// A case that violated one of the guarantees
+struct Failure {
+    index: u64,
+    mutations: Vec<Mutation>,
+    reason: String,
+    saved_as: PathBuf,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(case) = cli::flag_value(REPLAY_FLAG) {
+        replay(Path::new(&case));
+    }
+
+    println!("🧨 Fuzzing parse_binary_to_html_direct with Malformed .memscope Files");
+    println!("=====================================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    let cases = numeric_flag(CASES_FLAG, DEFAULT_CASES)?;
+    let budget_bytes = numeric_flag(MEMORY_BUDGET_FLAG, DEFAULT_MEMORY_BUDGET_MB)? * 1024 * 1024;
+    let timeout = Duration::from_millis(numeric_flag(TIMEOUT_FLAG, DEFAULT_TIMEOUT_MS)?);
+
+    let corpus = std::fs::read(SEED_CORPUS)
+        .map_err(|e| format!("Seed corpus {} unreadable: {}", SEED_CORPUS, e))?;
+    println!(
+        "   🌱 Seed corpus: {} bytes, {} records reachable",
+        corpus.len(),
+        binary_mutation::record_spans(&corpus).len()
+    );
+
+    let output_dir = PathBuf::from("./Memoryanalysis/binary_fuzz");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    let crash_dir = output_dir.join("crashes");
+    std::fs::create_dir_all(&crash_dir)?;
+    seed::record(&output_dir, seed)?;
+    let exe = std::env::current_exe()?;
+    let case_path = output_dir.join("current.memscope");
+
+    std::fs::write(&case_path, &corpus)?;
+    let (baseline, baseline_peak) = run_case(&exe, &case_path, timeout, u64::MAX)?;
+    if !matches!(baseline, Outcome::Accepted) {
+        println!("   ❌ FAILED: the unmutated seed corpus was not accepted");
+        return Err("unmutated seed corpus was not accepted".into());
+    }
+    let baseline_peak = baseline_peak.unwrap_or(0);
+    let limit = baseline_peak.saturating_add(budget_bytes);
+    println!(
+        "   💾 Baseline peak virtual memory {:.1} MB, limit {:.1} MB; {} ms per case",
+        baseline_peak as f64 / 1_048_576.0,
+        limit as f64 / 1_048_576.0,
+        timeout.as_millis()
+    );
+
+    let mut tallies: BTreeMap<Mutation, Tally> = BTreeMap::new();
+    let mut failures = Vec::new();
+    for index in 0..cases {
+        let mut rng = seed::stream_rng(seed, index as usize);
+        let mutations: Vec<Mutation> = (0..rng.random_range(1..=MAX_STACKED))
+            .map(|_| *Mutation::ALL.choose(&mut rng).expect("ALL is not empty"))
+            .collect();
+        let mut bytes = corpus.clone();
+        for mutation in &mutations {
+            mutation.apply(&mut bytes, &mut rng);
+        }
+        std::fs::write(&case_path, &bytes)?;
+
+        let (outcome, peak) = run_case(&exe, &case_path, timeout, limit)?;
+        let must_reject = mutations.iter().any(|m| m.always_invalid())
+            || (mutations.len() == 1 && mutations[0].invalid_alone());
+        let reason = match &outcome {
+            Outcome::Panicked => Some("panicked".to_string()),
+            Outcome::Died(status) => Some(format!("child died ({})", status)),
+            Outcome::Hung => Some(format!("no result after {} ms", timeout.as_millis())),
+            Outcome::OverBudget => Some(over_budget(peak, baseline_peak)),
+            _ if peak.is_some_and(|peak| peak > limit) => Some(over_budget(peak, baseline_peak)),
+            Outcome::Accepted if must_reject => Some("accepted a corrupt file".to_string()),
+            Outcome::Accepted | Outcome::Rejected => None,
+        };
+
+        let unique: BTreeSet<Mutation> = mutations.iter().copied().collect();
+        for mutation in unique {
+            let tally = tallies.entry(mutation).or_default();
+            tally.cases += 1;
+            match (&reason, &outcome) {
+                (Some(_), _) => tally.failed += 1,
+                (None, Outcome::Accepted) => tally.accepted += 1,
+                (None, _) => tally.rejected += 1,
+            }
+        }
+        if let Some(reason) = reason {
+            let saved_as = crash_dir.join(format!("case_{:04}.memscope", index));
+            std::fs::write(&saved_as, &bytes)?;
+            println!(
+                "   ❌ case {} [{}]: {}",
+                index,
+                mutation_names(&mutations),
+                reason
+            );
+            failures.push(Failure {
+                index,
+                mutations,
+                reason,
+                saved_as,
+            });
+        }
+        if (index + 1) % 100 == 0 {
+            println!("   🔄 {} / {} cases", index + 1, cases);
+        }
+    }
+    let _ = std::fs::remove_file(&case_path);
+    let _ = std::fs::remove_file(case_path.with_extension("html"));
+
+    let report = render_report(seed, cases, &tallies, &failures);
+    let report_path = output_dir.join("binary_fuzz.md");
+    std::fs::write(&report_path, report)?;
+    println!("   📄 Report: {}", report_path.display());
+
+    seed::announce(seed);
+    if failures.is_empty() {
+        println!(
+            "   ✅ SUCCESS: {} malformed files rejected or parsed without panics, hangs or runaway memory",
+            cases
+        );
+        Ok(())
+    } else {
+        println!(
+            "   ❌ FAILED: {} of {} cases, inputs saved under {}",
+            failures.len(),
+            cases,
+            crash_dir.display()
+        );
+        Err(format!("{} fuzz cases failed", failures.len()).into())
+    }
+}
+
+/// Parse `case`, print the result and this process's peak virtual memory,
+/// and exit with [`ACCEPTED_EXIT`] or [`REJECTED_EXIT`]
+fn replay(case: &Path) -> ! {
+    let html_path = case.with_extension("html");
+    let result = binary::parse_binary_to_html_direct(case, &html_path, "Fuzz Case");
+    if let Some(peak) = process_memory::peak_virtual_bytes_of(std::process::id()) {
+        println!("{} {}", PEAK_PREFIX, peak);
+    }
+    match result {
+        Ok(_) => {
+            println!("   ✅ accepted: {}", case.display());
+            std::process::exit(ACCEPTED_EXIT)
+        }
+        Err(e) => {
+            println!("   ℹ️  rejected: {}", e);
+            std::process::exit(REJECTED_EXIT)
+        }
+    }
+}
+
+/// Replay `case` in a child process, killing it once its peak virtual
+/// memory passes `limit` or it outlives `timeout`
+fn run_case(
+    exe: &Path,
+    case: &Path,
+    timeout: Duration,
+    limit: u64,
+) -> std::io::Result<(Outcome, Option<u64>)> {
+    let mut child = Command::new(exe)
+        .arg(REPLAY_FLAG)
+        .arg(case)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let started = Instant::now();
+    let mut peak = None;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            let mut stdout = String::new();
+            if let Some(mut pipe) = child.stdout.take() {
+                pipe.read_to_string(&mut stdout)?;
+            }
+            let reported = stdout
+                .lines()
+                .find_map(|line| line.strip_prefix(PEAK_PREFIX))
+                .and_then(|value| value.trim().parse().ok());
+            let outcome = match status.code() {
+                Some(ACCEPTED_EXIT) => Outcome::Accepted,
+                Some(REJECTED_EXIT) => Outcome::Rejected,
+                Some(PANIC_EXIT) => Outcome::Panicked,
+                _ => Outcome::Died(status.to_string()),
+            };
+            return Ok((outcome, peak.max(reported)));
+        }
+
+        peak = peak.max(process_memory::peak_virtual_bytes_of(child.id()));
+        let stopped = if peak.is_some_and(|peak| peak > limit) {
+            Some(Outcome::OverBudget)
+        } else if started.elapsed() > timeout {
+            Some(Outcome::Hung)
+        } else {
+            None
+        };
+        if let Some(outcome) = stopped {
+            let _ = child.kill();
+            child.wait()?;
+            return Ok((outcome, peak));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn numeric_flag(name: &str, default: u64) -> Result<u64, String> {
+    match cli::flag_value(name) {
+        Some(value) => value
+            .parse()
+            .map_err(|e| format!("Invalid {} value '{}': {}", name, value, e)),
+        None => Ok(default),
+    }
+}
+
+fn over_budget(peak: Option<u64>, baseline_peak: u64) -> String {
+    format!(
+        "peak virtual memory grew by {:.1} MB over the unmutated file",
+        peak.unwrap_or(0).saturating_sub(baseline_peak) as f64 / 1_048_576.0
+    )
+}
+
+fn mutation_names(mutations: &[Mutation]) -> String {
+    mutations
+        .iter()
+        .map(|mutation| mutation.name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn render_report(
+    seed: u64,
+    cases: u64,
+    tallies: &BTreeMap<Mutation, Tally>,
+    failures: &[Failure],
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "# Fuzzing parse_binary_to_html_direct\n");
+    let _ = writeln!(
+        report,
+        "- Seed: {} (reproduce with `{} {}`)",
+        seed,
+        seed::SEED_FLAG,
+        seed
+    );
+    let _ = writeln!(report, "- Seed corpus: `{}`", SEED_CORPUS);
+    let _ = writeln!(report, "- Cases: {}", cases);
+    let _ = writeln!(report, "- Failures: {}\n", failures.len());
+
+    let _ = writeln!(report, "## Outcomes by Mutation\n");
+    let _ = writeln!(
+        report,
+        "Cases stacking several mutations count once under each of them.\n"
+    );
+    let _ = writeln!(
+        report,
+        "| Mutation | Cases | Accepted | Rejected | Failed |"
+    );
+    let _ = writeln!(report, "|---|---|---|---|---|");
+    for (mutation, tally) in tallies {
+        let _ = writeln!(
+            report,
+            "| {} | {} | {} | {} | {} |",
+            mutation.name(),
+            tally.cases,
+            tally.accepted,
+            tally.rejected,
+            tally.failed
+        );
+    }
+
+    if !failures.is_empty() {
+        let _ = writeln!(report, "\n## Failures\n");
+        let _ = writeln!(
+            report,
+            "Replay one with `cargo run --bin binary_fuzz -- {} <file>`.\n",
+            REPLAY_FLAG
+        );
+        for failure in failures {
+            let _ = writeln!(
+                report,
+                "- case {} [{}]: {} (`{}`)",
+                failure.index,
+                mutation_names(&failure.mutations),
+                failure.reason,
+                failure.saved_as.display()
+            );
+        }
+    }
+    report
+}
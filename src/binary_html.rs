@@ -12,15 +12,36 @@
 //! - Borrow checker interactions
 //! - Clone optimization tracking
 //! - Ownership transfer patterns
+//!
+//! The allocations come from `scenarios/comprehensive_demo.toml`, compiled in;
+//! `--scenario-file <path>` runs another `.toml` or `.json` scenario instead
+//! (format in `scenario.rs`).
 
-use memscope_rs::core::types::{AllocationInfo, BorrowInfo, CloneInfo};
+use memscope_rs::core::types::AllocationInfo;
 use memscope_rs::export::binary;
+use memscope_stress_test::allocation_extensions::{self, TypeShape};
+use memscope_stress_test::html_report::{self, HtmlReport};
+use memscope_stress_test::scenario::{self, Scenario};
+use memscope_stress_test::{allocation, cli, js_syntax, report_data, report_roundtrip, seed};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use std::io::Write;
 use std::path::Path;
 use tempfile::TempDir;
 
+/// Replaces the built-in scenario with a `.toml` or `.json` file
+const SCENARIO_FLAG: &str = "--scenario-file";
+
+/// Title passed to `parse_binary_to_html_direct`
+const REPORT_TITLE: &str = "Comprehensive Memory Analysis Demo";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🚀 Comprehensive Binary to HTML Demo");
     println!("=====================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    let mut rng = seed::rng(seed);
 
     // Create temporary directory for output files
     let temp_dir = TempDir::new()?;
@@ -28,7 +49,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let html_path = temp_dir.path().join("comprehensive_report.html");
 
     // Generate comprehensive allocation data
-    let allocations = create_comprehensive_allocations();
+    let scenario = match cli::flag_value(SCENARIO_FLAG) {
+        Some(path) => scenario::load(Path::new(&path))?,
+        None => scenario::from_toml(scenario::COMPREHENSIVE_DEMO)?,
+    };
+    println!("📜 Scenario: {}", scenario.name);
+    let allocations = create_comprehensive_allocations(&scenario, &mut rng)?;
 
     println!(
         "📊 Generated {} allocations covering all trackable types",
@@ -41,15 +67,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     let binary_size = std::fs::metadata(&binary_path)?.len();
     println!("   Binary file size: {binary_size} bytes");
+    let read_back = verify_binary_round_trip(&binary_path, &allocations)?;
 
     // Convert binary to HTML using binary_dashboard.html template
     println!("🎨 Converting binary to HTML report...");
     println!("🔄 Calling parse_binary_to_html_direct...");
-    binary::parse_binary_to_html_direct(
-        &binary_path,
-        &html_path,
-        "Comprehensive Memory Analysis Demo",
-    )?;
+    binary::parse_binary_to_html_direct(&binary_path, &html_path, REPORT_TITLE)?;
     println!("✅ parse_binary_to_html_direct completed");
 
     let html_size = std::fs::metadata(&html_path)?.len();
@@ -67,591 +90,242 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     std::fs::copy(&binary_path, current_binary)?;
     std::fs::copy(&html_path, current_html)?;
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(current_html)?
+        .write_all(seed::html_comment(seed).as_bytes())?;
 
     println!("\n📋 Files copied to current directory:");
     println!("   📄 comprehensive_demo.memscope");
     println!("   🌐 comprehensive_report.html");
 
     // Analyze the generated HTML
-    analyze_html_content(current_html)?;
+    analyze_html_content(current_html, REPORT_TITLE, &read_back)?;
+    js_syntax::verify_report(current_html)?;
+    verify_round_trip(current_html, &allocations)?;
 
     println!(
         "\n🎯 Demo completed! Open 'comprehensive_report.html' in your browser to see the results."
     );
+    seed::announce(seed);
 
     Ok(())
 }
 
-/// Create comprehensive allocation data covering all trackable types and scenarios
-fn create_comprehensive_allocations() -> Vec<AllocationInfo> {
-    let mut allocations = Vec::new();
-    let mut ptr_counter = 0x1000;
-
-    // Helper to get next pointer
-    let mut next_ptr = || {
-        ptr_counter += 0x100;
-        ptr_counter
-    };
-
-    // 1. Basic Collections
-    allocations.push(create_allocation(
-        next_ptr(),
-        1024,
-        "user_data",
-        "Vec<User>",
-        "main",
-        "main",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 5,
-            mutable_borrows: 2,
-            max_concurrent_borrows: 3,
-            last_borrow_timestamp: Some(1234567890),
-        }),
-        Some(CloneInfo {
-            clone_count: 2,
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(150),
-    ));
-
-    // 2. Hash Map with Complex Key-Value Types
-    allocations.push(create_allocation(
-        next_ptr(),
-        2048,
-        "cache_storage",
-        "HashMap<String, Arc<Mutex<CacheEntry>>>",
-        "cache_module",
-        "worker_1",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 15,
-            mutable_borrows: 3,
-            max_concurrent_borrows: 8,
-            last_borrow_timestamp: Some(1234567920),
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // HashMap doesn't clone, it moves
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(300),
-    ));
-
-    // 3. Smart Pointer - Arc with Reference Counting
-    allocations.push(create_allocation(
-        next_ptr(),
-        512,
-        "shared_config",
-        "Arc<AppConfig>",
-        "config_manager",
-        "main",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 25, // Arc allows many immutable borrows
-            mutable_borrows: 0,    // Arc doesn't allow mutable borrows
-            max_concurrent_borrows: 25,
-            last_borrow_timestamp: Some(1234567950),
-        }),
-        Some(CloneInfo {
-            clone_count: 8, // Arc is frequently cloned
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(500),
-    ));
-
-    // 4. Box with Heap Allocation
-    allocations.push(create_allocation(
-        next_ptr(),
-        256,
-        "large_object",
-        "Box<LargeStruct>",
-        "processing_module",
-        "worker_2",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 3,
-            mutable_borrows: 1,
-            max_concurrent_borrows: 2,
-            last_borrow_timestamp: Some(1234567980),
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // Box moves, doesn't clone
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(75),
-    ));
-
-    // 5. RefCell with Interior Mutability
-    allocations.push(create_allocation(
-        next_ptr(),
-        128,
-        "mutable_state",
-        "Rc<RefCell<GameState>>",
-        "game_engine",
-        "main",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 8,
-            mutable_borrows: 4,
-            max_concurrent_borrows: 6,
-            last_borrow_timestamp: Some(1234568000),
-        }),
-        Some(CloneInfo {
-            clone_count: 5, // Rc is cloned
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(200),
-    ));
-
-    // 6. Generic Type with Multiple Parameters
-    allocations.push(create_allocation(
-        next_ptr(),
-        1536,
-        "generic_container",
-        "BTreeMap<UserId, Vec<Message<JsonValue>>>",
-        "messaging_system",
-        "async_runtime",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 12,
-            mutable_borrows: 2,
-            max_concurrent_borrows: 7,
-            last_borrow_timestamp: Some(1234568030),
-        }),
-        Some(CloneInfo {
-            clone_count: 1,
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(450),
-    ));
-
-    // 7. Trait Object with Dynamic Dispatch
-    allocations.push(create_allocation(
-        next_ptr(),
-        64,
-        "event_handler",
-        "Box<dyn EventHandler + Send + Sync>",
-        "event_system",
-        "event_loop",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 6,
-            mutable_borrows: 1,
-            max_concurrent_borrows: 4,
-            last_borrow_timestamp: Some(1234568060),
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // Trait objects can't be cloned
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(100),
-    ));
-
-    // 8. Async Context Allocation
-    allocations.push(create_allocation(
-        next_ptr(),
-        896,
-        "future_state",
-        "Pin<Box<dyn Future<Output = Result<Response, Error>>>>",
-        "async_handler",
-        "tokio_runtime",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 4,
-            mutable_borrows: 2,
-            max_concurrent_borrows: 3,
-            last_borrow_timestamp: Some(1234568090),
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // Futures are moved, not cloned
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(1200),
-    ));
-
-    // 9. FFI and Unsafe Allocation
-    allocations.push(create_allocation(
-        next_ptr(),
-        4096,
-        "ffi_buffer",
-        "*mut c_void",
-        "ffi_module",
-        "main",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 0, // Raw pointers don't participate in borrow checking
-            mutable_borrows: 0,
-            max_concurrent_borrows: 0,
-            last_borrow_timestamp: None,
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // Raw pointers are copied, not cloned
-            is_clone: false,
-            original_ptr: None,
-        }),
-        false, // No ownership history for raw pointers
-        None,  // Simplified
-        Some(800),
-    ));
-
-    // 10. Memory Leak Example
-    allocations.push(create_allocation(
-        next_ptr(),
-        2048,
-        "leaked_data",
-        "Vec<String>",
-        "buggy_function",
-        "worker_3",
-        true, // This is leaked
-        Some(BorrowInfo {
-            immutable_borrows: 2,
-            mutable_borrows: 1,
-            max_concurrent_borrows: 2,
-            last_borrow_timestamp: Some(1234568120),
-        }),
-        Some(CloneInfo {
-            clone_count: 1,
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        None, // No lifetime for leaked memory
-    ));
-
-    // 11. Circular Reference Detection
-    allocations.push(create_allocation(
-        next_ptr(),
-        384,
-        "node_a",
-        "Rc<RefCell<Node>>",
-        "graph_structure",
-        "main",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 6,
-            mutable_borrows: 3,
-            max_concurrent_borrows: 5,
-            last_borrow_timestamp: Some(1234568150),
-        }),
-        Some(CloneInfo {
-            clone_count: 4, // Rc cloned for circular references
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None, // Simplified
-        Some(600),
-    ));
-
-    // 12. High-Performance Buffer
-    allocations.push(create_allocation(
-        next_ptr(),
-        8192,
-        "ring_buffer",
-        "Vec<u8>",
-        "network_io",
-        "io_thread",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 20,
-            mutable_borrows: 8,
-            max_concurrent_borrows: 12,
-            last_borrow_timestamp: Some(1234568180),
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // Zero-copy operations
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None,     // Simplified
-        Some(50), // Very short-lived
-    ));
-
-    // 13. Clone-Heavy Data Structure
-    allocations.push(create_allocation(
-        next_ptr(),
-        1024,
-        "cloned_config",
-        "AppConfig",
-        "config_distribution",
-        "worker_4",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 8,
-            mutable_borrows: 0,
-            max_concurrent_borrows: 8,
-            last_borrow_timestamp: Some(1234568210),
-        }),
-        Some(CloneInfo {
-            clone_count: 15, // Heavily cloned
-            is_clone: true,
-            original_ptr: Some(0x1300), // Reference to original Arc<AppConfig>
-        }),
-        true,
-        None,     // Simplified
-        Some(25), // Short-lived clone
-    ));
-
-    // 14. Thread-Local Storage
-    allocations.push(create_allocation(
-        next_ptr(),
-        256,
-        "thread_local_cache",
-        "ThreadLocal<HashMap<String, CachedValue>>",
-        "thread_local_storage",
-        "worker_5",
-        false,
-        Some(BorrowInfo {
-            immutable_borrows: 12,
-            mutable_borrows: 4,
-            max_concurrent_borrows: 8,
-            last_borrow_timestamp: Some(1234568240),
-        }),
-        Some(CloneInfo {
-            clone_count: 0, // Thread-local data isn't cloned across threads
-            is_clone: false,
-            original_ptr: None,
-        }),
-        true,
-        None,       // Simplified
-        Some(2000), // Long-lived thread-local data
-    ));
-
-    // 15. Deallocated Memory (for comparison)
-    allocations.push(create_deallocated_allocation(
-        next_ptr(),
-        512,
-        "temp_buffer",
-        "Vec<u8>",
-        "temporary_processing",
-        "worker_1",
-        1234567890,
-        1234567990, // Deallocated after 100ms
-        Some(BorrowInfo {
-            immutable_borrows: 3,
-            mutable_borrows: 2,
-            max_concurrent_borrows: 3,
-            last_borrow_timestamp: Some(1234567985),
-        }),
-        Some(CloneInfo {
-            clone_count: 1,
-            is_clone: false,
-            original_ptr: None,
-        }),
-        100,
-    ));
+/// Build the scenario's allocations, vary them from the seed and fill in
+/// every extension field
+fn create_comprehensive_allocations(
+    scenario: &Scenario,
+    rng: &mut ChaCha8Rng,
+) -> Result<Vec<AllocationInfo>, String> {
+    let mut allocations = scenario.allocations()?;
 
     println!("📋 Created allocations covering:");
-    println!("   • Basic collections (Vec, HashMap, BTreeMap)");
-    println!("   • Smart pointers (Arc, Rc, Box, RefCell)");
-    println!("   • Generic types with complex parameters");
-    println!("   • Trait objects with dynamic dispatch");
-    println!("   • Async/Future contexts");
-    println!("   • FFI and unsafe allocations");
-    println!("   • Memory leaks and circular references");
-    println!("   • High-performance and clone-heavy scenarios");
-    println!("   • Thread-local storage");
-    println!("   • Deallocated memory examples");
-
-    allocations
-}
+    for item in &scenario.covers {
+        println!("   • {item}");
+    }
+    println!("   • Every optional extension field (layout, drop chain, access tracking, ...)");
 
-/// Create a standard allocation with all improve.md extensions
-#[allow(clippy::too_many_arguments)]
-fn create_allocation(
-    ptr: usize,
-    size: usize,
-    var_name: &str,
-    type_name: &str,
-    scope_name: &str,
-    thread_id: &str,
-    is_leaked: bool,
-    borrow_info: Option<BorrowInfo>,
-    clone_info: Option<CloneInfo>,
-    ownership_history_available: bool,
-    _extra_info: Option<()>, // Simplified
-    lifetime_ms: Option<u64>,
-) -> AllocationInfo {
-    AllocationInfo {
-        ptr,
-        size,
-        var_name: Some(var_name.to_string()),
-        type_name: Some(type_name.to_string()),
-        scope_name: Some(scope_name.to_string()),
-        timestamp_alloc: 1234567890,
-        timestamp_dealloc: None,
-        thread_id: thread_id.to_string(),
-        borrow_count: borrow_info
-            .as_ref()
-            .map(|b| b.immutable_borrows + b.mutable_borrows)
-            .unwrap_or(0),
-        stack_trace: Some(vec![
-            format!("{scope_name}::{var_name}"),
-            "main::run".to_string(),
-            "std::rt::lang_start".to_string(),
-        ]),
-        is_leaked,
-        lifetime_ms,
-        borrow_info,
-        clone_info,
-        ownership_history_available,
-        smart_pointer_info: None,     // Simplified for demo
-        memory_layout: None,          // Simplified for demo
-        generic_info: None,           // Simplified for demo
-        dynamic_type_info: None,      // Simplified for demo
-        runtime_state: None,          // Simplified for demo
-        stack_allocation: None,       // Simplified for demo
-        temporary_object: None,       // Simplified for demo
-        fragmentation_analysis: None, // Simplified for demo
-        generic_instantiation: None,  // Simplified for demo
-        type_relationships: None,     // Simplified for demo
-        type_usage: None,             // Simplified for demo
-        function_call_tracking: None, // Simplified for demo
-        lifecycle_tracking: None,     // Simplified for demo
-        access_tracking: None,        // Simplified for demo
-        drop_chain_analysis: None,    // Simplified for demo
+    apply_seeded_variation(&mut allocations, rng);
+
+    // Extensions are derived from the final sizes, lifetimes and threads
+    for allocation in allocations.iter_mut() {
+        let shape = TypeShape::parse(allocation.type_name.as_deref().unwrap_or_default())?;
+        allocation_extensions::populate(allocation, &shape);
     }
-}
 
-/// Create a deallocated allocation for comparison
-#[allow(clippy::too_many_arguments)]
-fn create_deallocated_allocation(
-    ptr: usize,
-    size: usize,
-    var_name: &str,
-    type_name: &str,
-    scope_name: &str,
-    thread_id: &str,
-    timestamp_alloc: u64,
-    timestamp_dealloc: u64,
-    borrow_info: Option<BorrowInfo>,
-    clone_info: Option<CloneInfo>,
-    lifetime_ms: u64,
-) -> AllocationInfo {
-    let mut allocation = create_allocation(
-        ptr,
-        size,
-        var_name,
-        type_name,
-        scope_name,
-        thread_id,
-        false,
-        borrow_info,
-        clone_info,
-        true,
-        None,
-        Some(lifetime_ms),
-    );
-    allocation.timestamp_alloc = timestamp_alloc;
-    allocation.timestamp_dealloc = Some(timestamp_dealloc);
-    allocation
+    Ok(allocations)
 }
 
-/// Extract type parameters from generic type names
-fn _extract_type_parameters(type_name: &str) -> Vec<String> {
-    if let Some(start) = type_name.find('<') {
-        if let Some(end) = type_name.rfind('>') {
-            let params = &type_name[start + 1..end];
-            return params.split(',').map(|s| s.trim().to_string()).collect();
+/// Vary sizes, lifetimes and worker thread assignment from the seeded RNG,
+/// keeping every allocation recognisable as its hand-written scenario
+fn apply_seeded_variation(allocations: &mut [AllocationInfo], rng: &mut ChaCha8Rng) {
+    for allocation in allocations.iter_mut() {
+        allocation.size = vary(rng, allocation.size as u64) as usize;
+        allocation.lifetime_ms = allocation.lifetime_ms.map(|ms| vary(rng, ms));
+        if let (Some(dealloc), Some(lifetime)) = (
+            allocation.timestamp_dealloc.as_mut(),
+            allocation.lifetime_ms,
+        ) {
+            *dealloc = allocation::dealloc_timestamp(allocation.timestamp_alloc, lifetime);
         }
     }
-    vec![]
-}
 
-/// Extract parent types from type names
-fn _extract_parent_types(type_name: &str) -> Vec<String> {
-    if type_name.contains("Arc") {
-        vec!["Arc".to_string()]
-    } else if type_name.contains("Rc") {
-        vec!["Rc".to_string()]
-    } else if type_name.contains("Box") {
-        vec!["Box".to_string()]
-    } else if type_name.contains("Vec") {
-        vec!["Vec".to_string()]
-    } else if type_name.contains("HashMap") {
-        vec!["HashMap".to_string()]
-    } else {
-        vec![]
+    // Reassign worker_N labels through a seeded permutation of the workers
+    let mut workers: Vec<String> = allocations
+        .iter()
+        .map(|a| a.thread_id.clone())
+        .filter(|t| t.starts_with("worker_"))
+        .collect();
+    workers.sort();
+    workers.dedup();
+
+    let mut shuffled = workers.clone();
+    shuffled.shuffle(rng);
+
+    for allocation in allocations.iter_mut() {
+        if let Some(idx) = workers.iter().position(|w| *w == allocation.thread_id) {
+            allocation.thread_id = shuffled[idx].clone();
+        }
     }
 }
 
-/// Extract associated types from type names
-fn _extract_associated_types(type_name: &str) -> Vec<String> {
-    let mut types = vec![];
-    if type_name.contains("Iterator") {
-        types.push("Item".to_string());
-    }
-    if type_name.contains("Future") {
-        types.push("Output".to_string());
-    }
-    if type_name.contains("Result") {
-        types.push("Error".to_string());
-    }
-    types
+/// Seeded value within 50%-150% of `nominal`
+fn vary(rng: &mut ChaCha8Rng, nominal: u64) -> u64 {
+    (nominal / 2 + rng.random_range(0..=nominal)).max(1)
+}
+
+/// Read the exported file back and diff every allocation against the input
+///
+/// The binary format stores the core `AllocationInfo` fields only, so the
+/// extension fields `populate` fills are listed as lost rather than failing.
+/// Returns the decoded allocations the HTML conversion starts from.
+fn verify_binary_round_trip(
+    binary_path: &Path,
+    allocations: &[AllocationInfo],
+) -> Result<Vec<AllocationInfo>, Box<dyn std::error::Error>> {
+    let read_back = binary::BinaryReader::new(binary_path)?.read_all()?;
+    let mismatches = report_roundtrip::diff_read_back(allocations, &read_back);
+    report_roundtrip::report_mismatches("allocations read back", allocations.len(), &mismatches)?;
+    let lost = report_roundtrip::diff_lost_extensions(allocations, &read_back);
+    report_roundtrip::report_lost_extensions(allocations.len(), &lost);
+    Ok(read_back)
+}
+
+/// Deserialize the embedded allocations and diff them against the input
+fn verify_round_trip(
+    html_path: &Path,
+    allocations: &[AllocationInfo],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(html_path)?;
+    let embedded = report_roundtrip::embedded_allocations(&content)?;
+    let mismatches = report_roundtrip::diff_allocations(allocations, &embedded);
+    report_roundtrip::report_mismatches("embedded allocations", allocations.len(), &mismatches)?;
+    Ok(())
 }
 
-/// Analyze the generated HTML content
-fn analyze_html_content(html_path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// Inspect the generated dashboard's DOM and embedded data against the
+/// allocations decoded from the binary it was converted from
+fn analyze_html_content(
+    html_path: &Path,
+    title: &str,
+    decoded: &[AllocationInfo],
+) -> Result<(), Box<dyn std::error::Error>> {
     let content = std::fs::read_to_string(html_path)?;
+    let report = HtmlReport::parse(&content);
+    let mut problems = Vec::new();
 
     println!("\n📊 HTML Analysis:");
     println!("   File size: {} bytes", content.len());
 
-    // Count different sections
-    let allocation_count = content.matches("ptr").count();
-    let borrow_info_count = content.matches("borrow_info").count();
-    let clone_info_count = content.matches("clone_info").count();
-    let smart_pointer_count = content.matches("smart_pointer").count();
+    let parse_errors = report.parse_errors();
+    if !parse_errors.is_empty() {
+        println!(
+            "   ⚠️  HTML parser recovered from {} error(s): {}",
+            parse_errors.len(),
+            parse_errors.join(", ")
+        );
+    }
+
+    // Title and headings must carry the requested title
+    let page_title = report.title().unwrap_or_default();
+    if page_title.starts_with(title) {
+        println!("   ✅ <title>: {page_title}");
+    } else {
+        problems.push(format!(
+            "<title> is '{page_title}', expected it to start with '{title}'"
+        ));
+    }
+    let h1 = report.headings("h1");
+    if h1.iter().any(|heading| heading.contains(title)) {
+        println!("   ✅ <h1>: {}", h1.join(" | "));
+    } else {
+        problems.push(format!("no <h1> contains '{title}' (found {h1:?})"));
+    }
+    println!("   Sections (h2): {}", report.headings("h2").len());
 
-    println!("   Allocations displayed: {allocation_count}");
-    println!("   Borrow info entries: {borrow_info_count}");
-    println!("   Clone info entries: {clone_info_count}");
-    println!("   Smart pointer entries: {smart_pointer_count}");
+    for id in report.missing_ids(&html_report::BINARY_DASHBOARD_SECTION_IDS) {
+        problems.push(format!("required section #{id} is missing"));
+    }
+
+    // Table bodies are filled by script from the embedded data
+    let tables = report.tables();
+    for table in &tables {
+        println!(
+            "   Table #{}: {} header row(s), {} static body row(s)",
+            table.id.as_deref().unwrap_or("?"),
+            table.header_rows,
+            table.body_rows
+        );
+    }
+    if !tables.iter().any(|t| t.id.as_deref() == Some("allocTable")) {
+        problems.push("allocation table #allocTable is missing".to_string());
+    }
+
+    match report_data::embedded_object(&content, report_data::ANALYSIS_DATA) {
+        Ok(data) => {
+            let embedded = data["allocations"].as_array().cloned().unwrap_or_default();
+            let with_field = |field: &str| embedded.iter().filter(|a| !a[field].is_null()).count();
+            let counts = [
+                ("Allocation rows", embedded.len(), decoded.len()),
+                (
+                    "Borrow info entries",
+                    with_field("borrow_info"),
+                    decoded.iter().filter(|a| a.borrow_info.is_some()).count(),
+                ),
+                (
+                    "Clone info entries",
+                    with_field("clone_info"),
+                    decoded.iter().filter(|a| a.clone_info.is_some()).count(),
+                ),
+            ];
+            for (label, found, expected) in counts {
+                if found == expected {
+                    println!("   ✅ {label}: {found}");
+                } else {
+                    problems.push(format!(
+                        "{label}: {found} in report, {expected} decoded from the binary"
+                    ));
+                }
+            }
+        }
+        Err(e) => problems.push(e),
+    }
 
-    // Check for key features
     let features = [
-        ("Modern CSS Grid", content.contains("stats-grid")),
-        ("Interactive Table", content.contains("allocations-table")),
-        ("JavaScript Data", content.contains("const allocations")),
-        ("Memory Formatting", content.contains("formatSize")),
         (
-            "Project Branding",
-            content.contains("Comprehensive Memory Analysis Demo"),
+            "Responsive Design",
+            !report.select("meta[name=viewport]")?.is_empty(),
+        ),
+        (
+            "Interactive Table",
+            !report.select("table tbody#allocTable")?.is_empty(),
+        ),
+        (
+            "Memory Formatting",
+            report.script_source().contains("formatSize"),
         ),
-        ("Responsive Design", content.contains("viewport")),
-        ("Clean Template", content.contains("Memory Analysis Report")),
     ];
-
     println!("   Features detected:");
     for (feature, present) in features {
         println!("     {} {}", if present { "✅" } else { "❌" }, feature);
+        if !present {
+            problems.push(format!("feature missing: {feature}"));
+        }
     }
 
-    Ok(())
+    if problems.is_empty() {
+        println!("   ✅ Report structure matches the exported data");
+        Ok(())
+    } else {
+        for problem in &problems {
+            println!("   ❌ {problem}");
+        }
+        Err(format!(
+            "{} structural problem(s) in {}",
+            problems.len(),
+            html_path.display()
+        )
+        .into())
+    }
 }
@@ -0,0 +1,228 @@
+//! Structure-aware mutations of `.memscope` files
+//!
+//! Offsets follow the layout of files written by `export_to_binary`, as seen
+//! in `Memoryanalysis/comprehensive_demo.memscope` (integers little-endian):
+//! - `0x00` magic `MEMSCOPE`, `0x08` format version (u32), `0x0c` record
+//!   count (u32), remaining header fields up to `0x20`
+//! - `0x20` string table marker (`NONE` when no table is written) followed by
+//!   its entry count (u32)
+//! - records from `0x28`: a tag byte, the body length (u32) and the body,
+//!   whose strings are u32 length-prefixed UTF-8
+//!
+//! A mutation whose landmark is gone (an earlier mutation truncated the file
+//! or broke the record chain) falls back to byte flips.
+
+use rand::seq::IndexedRandom;
+use rand::Rng;
+
+use std::ops::Range;
+
+const MAGIC: Range<usize> = 0..8;
+const VERSION_OFFSET: usize = 0x08;
+const RECORD_COUNT_OFFSET: usize = 0x0c;
+const HEADER_FIELDS: Range<usize> = 0x10..0x20;
+const STRING_TABLE_MARKER: Range<usize> = 0x20..0x24;
+const STRING_TABLE_COUNT_OFFSET: usize = 0x24;
+const RECORDS_OFFSET: usize = 0x28;
+
+/// Longest string whose length prefix is recognised as one
+const MAX_STRING_LEN: u32 = 4096;
+
+/// Values that trip off-by-one, sign and capacity arithmetic
+const INTERESTING_U32: [u32; 9] = [
+    0,
+    1,
+    0x7f,
+    0xff,
+    0xffff,
+    0x7fff_ffff,
+    0x8000_0000,
+    0xffff_fffe,
+    u32::MAX,
+];
+
+/// One way of corrupting a `.memscope` file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Mutation {
+    Magic,
+    Version,
+    RecordCount,
+    HeaderField,
+    StringTableMarker,
+    StringTableCount,
+    RecordTag,
+    RecordLength,
+    StringLength,
+    Truncate,
+    DropRecord,
+    DuplicateRecord,
+    ByteFlip,
+}
+
+impl Mutation {
+    pub const ALL: [Mutation; 13] = [
+        Mutation::Magic,
+        Mutation::Version,
+        Mutation::RecordCount,
+        Mutation::HeaderField,
+        Mutation::StringTableMarker,
+        Mutation::StringTableCount,
+        Mutation::RecordTag,
+        Mutation::RecordLength,
+        Mutation::StringLength,
+        Mutation::Truncate,
+        Mutation::DropRecord,
+        Mutation::DuplicateRecord,
+        Mutation::ByteFlip,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Mutation::Magic => "magic",
+            Mutation::Version => "version",
+            Mutation::RecordCount => "record_count",
+            Mutation::HeaderField => "header_field",
+            Mutation::StringTableMarker => "string_table_marker",
+            Mutation::StringTableCount => "string_table_count",
+            Mutation::RecordTag => "record_tag",
+            Mutation::RecordLength => "record_length",
+            Mutation::StringLength => "string_length",
+            Mutation::Truncate => "truncate",
+            Mutation::DropRecord => "drop_record",
+            Mutation::DuplicateRecord => "duplicate_record",
+            Mutation::ByteFlip => "byte_flip",
+        }
+    }
+
+    /// Whether no reader may accept the result, whatever else was mutated
+    pub fn always_invalid(self) -> bool {
+        matches!(self, Mutation::Magic)
+    }
+
+    /// Whether a reader must reject the result when this is the only mutation
+    pub fn invalid_alone(self) -> bool {
+        matches!(self, Mutation::Magic | Mutation::Truncate)
+    }
+
+    /// Corrupt `bytes` in place
+    pub fn apply<R: Rng>(self, bytes: &mut Vec<u8>, rng: &mut R) {
+        let records = record_spans(bytes);
+        match self {
+            Mutation::Magic if bytes.len() >= MAGIC.end => {
+                let offset = rng.random_range(MAGIC);
+                bytes[offset] ^= rng.random_range(1..=u8::MAX);
+            }
+            Mutation::Version => write_u32(bytes, VERSION_OFFSET, rng),
+            Mutation::RecordCount => write_u32(bytes, RECORD_COUNT_OFFSET, rng),
+            Mutation::HeaderField if bytes.len() >= HEADER_FIELDS.end => {
+                let offset = rng.random_range(HEADER_FIELDS);
+                bytes[offset] = rng.random();
+            }
+            Mutation::StringTableMarker if bytes.len() >= STRING_TABLE_MARKER.end => {
+                for byte in &mut bytes[STRING_TABLE_MARKER] {
+                    *byte = rng.random_range(b'A'..=b'Z');
+                }
+            }
+            Mutation::StringTableCount => write_u32(bytes, STRING_TABLE_COUNT_OFFSET, rng),
+            Mutation::RecordTag if !records.is_empty() => {
+                let record = records.choose(rng).expect("records is not empty");
+                bytes[record.start] = rng.random();
+            }
+            Mutation::RecordLength if !records.is_empty() => {
+                let record = records.choose(rng).expect("records is not empty");
+                write_u32(bytes, record.start + 1, rng);
+            }
+            Mutation::StringLength => match string_length_offsets(bytes, &records).choose(rng) {
+                Some(&offset) => write_u32(bytes, offset, rng),
+                None => flip_bytes(bytes, rng),
+            },
+            Mutation::Truncate if !bytes.is_empty() => {
+                let len = rng.random_range(0..bytes.len());
+                bytes.truncate(len);
+            }
+            Mutation::DropRecord if !records.is_empty() => {
+                let record = records.choose(rng).expect("records is not empty");
+                bytes.drain(record.clone());
+            }
+            Mutation::DuplicateRecord if !records.is_empty() => {
+                let record = records.choose(rng).expect("records is not empty");
+                let copy = bytes[record.clone()].to_vec();
+                bytes.splice(record.end..record.end, copy);
+            }
+            _ => flip_bytes(bytes, rng),
+        }
+    }
+}
+
+/// Byte ranges of every record reachable by following record lengths
+pub fn record_spans(bytes: &[u8]) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut start = RECORDS_OFFSET;
+    while let Some(body_len) = read_u32(bytes, start + 1) {
+        let end = start + 5 + body_len as usize;
+        if end > bytes.len() {
+            break;
+        }
+        spans.push(start..end);
+        start = end;
+    }
+    spans
+}
+
+/// Offsets of u32 prefixes inside records that are followed by that many
+/// bytes of printable UTF-8
+fn string_length_offsets(bytes: &[u8], records: &[Range<usize>]) -> Vec<usize> {
+    records
+        .iter()
+        .flat_map(|record| record.start + 5..record.end.saturating_sub(4))
+        .filter(|&offset| {
+            let Some(len) = read_u32(bytes, offset) else {
+                return false;
+            };
+            if !(2..=MAX_STRING_LEN).contains(&len) {
+                return false;
+            }
+            bytes
+                .get(offset + 4..offset + 4 + len as usize)
+                .and_then(|text| std::str::from_utf8(text).ok())
+                .is_some_and(|text| !text.chars().any(char::is_control))
+        })
+        .collect()
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let field = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(field.try_into().ok()?))
+}
+
+/// Overwrite the u32 at `offset` with an interesting value that differs from
+/// the current one, or flip bytes when the field is out of range
+fn write_u32<R: Rng>(bytes: &mut [u8], offset: usize, rng: &mut R) {
+    let Some(current) = read_u32(bytes, offset) else {
+        flip_bytes(bytes, rng);
+        return;
+    };
+    let mut value = if rng.random_bool(0.75) {
+        *INTERESTING_U32
+            .choose(rng)
+            .expect("INTERESTING_U32 is not empty")
+    } else {
+        current
+            .wrapping_add(rng.random_range(1..=2))
+            .wrapping_mul(rng.random_range(1..=4))
+    };
+    if value == current {
+        value = !current;
+    }
+    bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn flip_bytes<R: Rng>(bytes: &mut [u8], rng: &mut R) {
+    if bytes.is_empty() {
+        return;
+    }
+    for _ in 0..rng.random_range(1..=8) {
+        let offset = rng.random_range(0..bytes.len());
+        bytes[offset] ^= rng.random_range(1..=u8::MAX);
+    }
+}
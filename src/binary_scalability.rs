@@ -0,0 +1,399 @@
+//! Binary Export and HTML Conversion at 10k → 1M Allocations
+//!
+//! This scenario exports seeded `AllocationInfo` sets of growing size with
+//! `export_to_binary`, converts each file with `parse_binary_to_html_direct`,
+//! and for both steps records:
+//! 1. Throughput in allocations per second
+//! 2. Peak resident memory growth
+//! 3. Size of the output file
+//!
+//! Every run is appended to `Memoryanalysis/binary_scalability_history.jsonl`.
+//! The run fails when time, peak memory or output size grows superlinearly
+//! with the allocation count; drops against the previous run are reported as
+//! warnings, since timings from different machines are not comparable.
+//! `--max-allocations` caps the largest set (default 1,000,000).
+
+use memscope_rs::export::binary;
+use memscope_stress_test::allocation::synthetic_allocations;
+use memscope_stress_test::{cli, process_memory, seed};
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Write as _;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Allocation counts measured, smallest first
+const ALLOCATION_COUNTS: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+/// Threads the allocations are spread over
+const THREADS: usize = 32;
+
+/// Fitted log-log growth exponent above which growth counts as superlinear
+const SUPERLINEAR_EXPONENT: f64 = 1.2;
+
+/// Relative worsening against the previous run that is worth a warning
+const REGRESSION_TOLERANCE: f64 = 0.25;
+
+/// Flag capping the largest allocation count
+const MAX_ALLOCATIONS_FLAG: &str = "--max-allocations";
+
+/// Results of every run, one JSON object per line
+const HISTORY_PATH: &str = "./Memoryanalysis/binary_scalability_history.jsonl";
+
+/// Measurements of one step for one allocation count
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct StepMeasurement {
+    millis: f64,
+    /// Peak RSS growth over the RSS before the step, if readable
+    peak_memory_bytes: Option<u64>,
+    output_bytes: u64,
+}
+
+/// Both steps for one allocation count
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+struct ScalePoint {
+    allocations: usize,
+    export: StepMeasurement,
+    html: StepMeasurement,
+}
+
+impl ScalePoint {
+    fn throughput(&self, step: &StepMeasurement) -> f64 {
+        self.allocations as f64 / (step.millis / 1000.0).max(f64::EPSILON)
+    }
+}
+
+/// Reads one measured quantity from a point, if it was measured
+type Metric = fn(&ScalePoint) -> Option<f64>;
+
+/// One line of the history file
+#[derive(Debug, Serialize, Deserialize)]
+struct HistoryEntry {
+    recorded_at: String,
+    seed: u64,
+    points: Vec<ScalePoint>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🏋️  Binary Export and HTML Conversion at 10k → 1M Allocations");
+    println!("==============================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let max_allocations = match cli::flag_value(MAX_ALLOCATIONS_FLAG) {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|e| format!("Invalid {} value '{}': {}", MAX_ALLOCATIONS_FLAG, value, e))?,
+        None => *ALLOCATION_COUNTS.last().expect("counts are not empty"),
+    };
+
+    let output_dir = PathBuf::from("./Memoryanalysis/binary_scalability");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let mut points = Vec::new();
+    for (idx, &count) in ALLOCATION_COUNTS.iter().enumerate() {
+        if count > max_allocations {
+            println!(
+                "   ⏭️  {:>9} allocations: skipped by {}",
+                count, MAX_ALLOCATIONS_FLAG
+            );
+            continue;
+        }
+        let allocations = synthetic_allocations(count, THREADS, &mut seed::stream_rng(seed, idx));
+        let binary_path = output_dir.join(format!("allocations_{}.memscope", count));
+        let html_path = output_dir.join(format!("allocations_{}.html", count));
+
+        let export = measure(&binary_path, || {
+            binary::export_to_binary(&allocations, &binary_path)
+        })?;
+        drop(allocations);
+        let html = measure(&html_path, || {
+            binary::parse_binary_to_html_direct(
+                &binary_path,
+                &html_path,
+                &format!("{} Allocations", count),
+            )
+        })?;
+
+        let point = ScalePoint {
+            allocations: count,
+            export,
+            html,
+        };
+        println!(
+            "   📦 {:>9} allocations: export {:>10.0}/s peak +{} → {:.2} MB | HTML {:>10.0}/s peak +{} → {:.2} MB",
+            count,
+            point.throughput(&point.export),
+            format_megabytes(point.export.peak_memory_bytes),
+            point.export.output_bytes as f64 / (1024.0 * 1024.0),
+            point.throughput(&point.html),
+            format_megabytes(point.html.peak_memory_bytes),
+            point.html.output_bytes as f64 / (1024.0 * 1024.0)
+        );
+        points.push(point);
+
+        // Large outputs are only kept long enough to be measured
+        let _ = std::fs::remove_file(&binary_path);
+        let _ = std::fs::remove_file(&html_path);
+    }
+
+    let mut failures = Vec::new();
+    let metrics: [(&str, Metric); 6] = [
+        ("export time", |p| Some(p.export.millis)),
+        ("export peak memory", |p| {
+            p.export.peak_memory_bytes.map(|b| b as f64)
+        }),
+        ("binary size", |p| Some(p.export.output_bytes as f64)),
+        ("HTML time", |p| Some(p.html.millis)),
+        ("HTML peak memory", |p| {
+            p.html.peak_memory_bytes.map(|b| b as f64)
+        }),
+        ("HTML size", |p| Some(p.html.output_bytes as f64)),
+    ];
+    let mut exponents = Vec::new();
+    for (metric, value) in metrics {
+        let exponent = growth_exponent(&points, value);
+        match exponent {
+            Some(exponent) if exponent > SUPERLINEAR_EXPONENT => {
+                println!("   ❌ {} grows as n^{:.2}", metric, exponent);
+                failures.push(format!(
+                    "{} grows superlinearly (n^{:.2})",
+                    metric, exponent
+                ));
+            }
+            Some(exponent) => println!("   ✅ {} grows as n^{:.2}", metric, exponent),
+            None => println!("   ⚠️  {} growth could not be measured", metric),
+        }
+        exponents.push((metric, exponent));
+    }
+
+    let previous = last_history_entry(Path::new(HISTORY_PATH))?;
+    let regressions = match &previous {
+        Some(previous) => compare_with_previous(&points, previous),
+        None => {
+            println!("   ℹ️  No previous run in {}", HISTORY_PATH);
+            Vec::new()
+        }
+    };
+    for regression in &regressions {
+        println!("   ⚠️  {}", regression);
+    }
+    append_history_entry(
+        Path::new(HISTORY_PATH),
+        &HistoryEntry {
+            recorded_at: chrono::Utc::now().to_rfc3339(),
+            seed,
+            points: points.clone(),
+        },
+    )?;
+    println!("   🗂️  Appended results to {}", HISTORY_PATH);
+
+    let report_path = output_dir.join("binary_scalability.md");
+    std::fs::write(
+        &report_path,
+        render_report(&points, &exponents, &regressions, previous.as_ref(), seed),
+    )?;
+    println!("\n📄 Scalability report: {}", report_path.display());
+
+    seed::announce(seed);
+    if failures.is_empty() {
+        println!("   ✅ SUCCESS: binary export and HTML conversion scale linearly");
+        Ok(())
+    } else {
+        println!("   ❌ FAILED: {} scalability problem(s)", failures.len());
+        Err(failures.join("; ").into())
+    }
+}
+
+/// Time `step`, track its peak RSS growth, and read the size of `output`
+fn measure<E>(output: &Path, step: impl FnOnce() -> Result<(), E>) -> Result<StepMeasurement, E>
+where
+    E: Into<Box<dyn std::error::Error>>,
+{
+    let baseline_rss = process_memory::current_rss_bytes();
+    let peak_reset = process_memory::reset_peak_rss();
+    let started = Instant::now();
+    step()?;
+    let elapsed = started.elapsed();
+    let peak_memory_bytes = match (peak_reset, baseline_rss, process_memory::peak_rss_bytes()) {
+        (true, Some(baseline), Some(peak)) => Some(peak.saturating_sub(baseline)),
+        _ => None,
+    };
+    Ok(StepMeasurement {
+        millis: elapsed.as_secs_f64() * 1000.0,
+        peak_memory_bytes,
+        output_bytes: std::fs::metadata(output).map_or(0, |m| m.len()),
+    })
+}
+
+/// Least-squares slope of `ln(metric)` against `ln(allocations)`; 1.0 is linear
+fn growth_exponent<F>(points: &[ScalePoint], metric: F) -> Option<f64>
+where
+    F: Fn(&ScalePoint) -> Option<f64>,
+{
+    let samples: Vec<(f64, f64)> = points
+        .iter()
+        .filter_map(|p| {
+            metric(p)
+                .filter(|&value| value > 0.0)
+                .map(|value| ((p.allocations as f64).ln(), value.ln()))
+        })
+        .collect();
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let n = samples.len() as f64;
+    let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = samples
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = samples.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    Some(covariance / variance)
+}
+
+/// Throughput drops and output growth beyond [`REGRESSION_TOLERANCE`]
+/// against the previous run, for every allocation count both runs measured
+fn compare_with_previous(points: &[ScalePoint], previous: &HistoryEntry) -> Vec<String> {
+    let mut regressions = Vec::new();
+    for point in points {
+        let Some(before) = previous
+            .points
+            .iter()
+            .find(|p| p.allocations == point.allocations)
+        else {
+            continue;
+        };
+        for (step, now, then) in [
+            ("export", &point.export, &before.export),
+            ("HTML", &point.html, &before.html),
+        ] {
+            let throughput = point.throughput(now);
+            let previous_throughput = before.throughput(then);
+            if throughput < previous_throughput * (1.0 - REGRESSION_TOLERANCE) {
+                regressions.push(format!(
+                    "{} allocations: {} throughput fell from {:.0}/s to {:.0}/s",
+                    point.allocations, step, previous_throughput, throughput
+                ));
+            }
+            if now.output_bytes as f64 > then.output_bytes as f64 * (1.0 + REGRESSION_TOLERANCE) {
+                regressions.push(format!(
+                    "{} allocations: {} output grew from {} to {} bytes",
+                    point.allocations, step, then.output_bytes, now.output_bytes
+                ));
+            }
+        }
+    }
+    regressions
+}
+
+fn last_history_entry(path: &Path) -> Result<Option<HistoryEntry>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let history = std::fs::read_to_string(path)?;
+    match history.lines().rev().find(|line| !line.trim().is_empty()) {
+        Some(line) => Ok(Some(serde_json::from_str(line).map_err(|e| {
+            format!("Unreadable last entry in {}: {}", path.display(), e)
+        })?)),
+        None => Ok(None),
+    }
+}
+
+fn append_history_entry(path: &Path, entry: &HistoryEntry) -> std::io::Result<()> {
+    let mut history = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(history, "{}", serde_json::to_string(entry)?)
+}
+
+fn format_megabytes(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(bytes) => format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0)),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Render every measurement, the growth exponents and the comparison with
+/// the previous run as markdown
+fn render_report(
+    points: &[ScalePoint],
+    exponents: &[(&str, Option<f64>)],
+    regressions: &[String],
+    previous: Option<&HistoryEntry>,
+    seed: u64,
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "# Binary Export and HTML Conversion Scalability\n");
+    let _ = writeln!(
+        report,
+        "- Seed: {seed} (reproduce with `{} {seed}`)",
+        seed::SEED_FLAG
+    );
+    let _ = writeln!(report, "- Threads: {THREADS}");
+    let _ = writeln!(report, "- History: `{HISTORY_PATH}`\n");
+
+    let _ = writeln!(
+        report,
+        "| Allocations | Export (alloc/s) | Export peak (MB) | Binary (MB) | HTML (alloc/s) | HTML peak (MB) | HTML (MB) | HTML bytes per allocation |"
+    );
+    let _ = writeln!(report, "|---:|---:|---:|---:|---:|---:|---:|---:|");
+    for point in points {
+        let _ = writeln!(
+            report,
+            "| {} | {:.0} | {} | {:.2} | {:.0} | {} | {:.2} | {:.0} |",
+            point.allocations,
+            point.throughput(&point.export),
+            format_megabytes(point.export.peak_memory_bytes).trim_end_matches(" MB"),
+            point.export.output_bytes as f64 / (1024.0 * 1024.0),
+            point.throughput(&point.html),
+            format_megabytes(point.html.peak_memory_bytes).trim_end_matches(" MB"),
+            point.html.output_bytes as f64 / (1024.0 * 1024.0),
+            point.html.output_bytes as f64 / point.allocations as f64
+        );
+    }
+
+    let _ = writeln!(
+        report,
+        "\n## Growth Exponents (limit {SUPERLINEAR_EXPONENT})\n"
+    );
+    for (metric, exponent) in exponents {
+        let exponent = exponent.map_or_else(|| "n/a".to_string(), |e| format!("{:.2}", e));
+        let _ = writeln!(report, "- {}: {}", metric, exponent);
+    }
+
+    let _ = writeln!(report, "\n## Against the Previous Run\n");
+    match previous {
+        None => {
+            let _ = writeln!(report, "No previous run recorded.");
+        }
+        Some(previous) if regressions.is_empty() => {
+            let _ = writeln!(
+                report,
+                "No change beyond {:.0}% since {} (seed {}).",
+                REGRESSION_TOLERANCE * 100.0,
+                previous.recorded_at,
+                previous.seed
+            );
+        }
+        Some(previous) => {
+            let _ = writeln!(
+                report,
+                "Compared with {} (seed {}):\n",
+                previous.recorded_at, previous.seed
+            );
+            for regression in regressions {
+                let _ = writeln!(report, "- ⚠️ {}", regression);
+            }
+        }
+    }
+    report
+}
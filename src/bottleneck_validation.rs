@@ -0,0 +1,300 @@
+//! Performance Insight Validation with Deliberately Skewed Workloads
+//!
+//! hybrid_example prints `performance_insights.primary_bottleneck` without
+//! judging it. This scenario runs three workloads that are each dominated by a
+//! single resource through `IntegratedProfilingSession` and checks that the
+//! reported bottleneck matches the workload:
+//! 1. Pure compute - tight arithmetic loops, no allocations or I/O
+//! 2. Allocation churn - tracked allocate/free cycles with a large live set
+//! 3. fsync-heavy file I/O - small writes each followed by `sync_all`
+
+use memscope_rs::lockfree::tracker::{
+    finalize_thread_tracker, init_thread_tracker, track_allocation_lockfree,
+    track_deallocation_lockfree, SamplingConfig,
+};
+use memscope_rs::lockfree::{BottleneckType, IntegratedProfilingSession};
+use memscope_stress_test::seed;
+use rand::Rng;
+use tempfile::NamedTempFile;
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long each workload keeps its threads busy
+const WORKLOAD_DURATION: Duration = Duration::from_secs(3);
+
+/// A workload dominated by one resource and the bottleneck it should produce
+#[derive(Debug, Clone, Copy)]
+enum SkewedWorkload {
+    Compute,
+    AllocationChurn,
+    FsyncIo,
+}
+
+impl SkewedWorkload {
+    const ALL: [SkewedWorkload; 3] = [
+        SkewedWorkload::Compute,
+        SkewedWorkload::AllocationChurn,
+        SkewedWorkload::FsyncIo,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            SkewedWorkload::Compute => "compute",
+            SkewedWorkload::AllocationChurn => "allocation_churn",
+            SkewedWorkload::FsyncIo => "fsync_io",
+        }
+    }
+
+    /// Name of the bottleneck the workload should produce
+    fn expected_bottleneck(self) -> &'static str {
+        match self {
+            SkewedWorkload::Compute => "CpuBound",
+            SkewedWorkload::AllocationChurn => "MemoryBound",
+            SkewedWorkload::FsyncIo => "IoBound",
+        }
+    }
+
+    fn produces(self, bottleneck: &BottleneckType) -> bool {
+        match self {
+            SkewedWorkload::Compute => matches!(bottleneck, BottleneckType::CpuBound),
+            SkewedWorkload::AllocationChurn => matches!(bottleneck, BottleneckType::MemoryBound),
+            SkewedWorkload::FsyncIo => matches!(bottleneck, BottleneckType::IoBound),
+        }
+    }
+}
+
+/// Outcome of one profiled workload
+struct ValidationResult {
+    workload: SkewedWorkload,
+    reported_bottleneck: BottleneckType,
+    cpu_efficiency: f64,
+    memory_efficiency: f64,
+    operations: usize,
+}
+
+impl ValidationResult {
+    fn matches(&self) -> bool {
+        self.workload.produces(&self.reported_bottleneck)
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🎯 Primary Bottleneck Validation with Skewed Workloads");
+    println!("=====================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let threads = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4);
+    println!("   🧵 Worker threads per workload: {}", threads);
+    println!("   ⏱️  Duration per workload: {:?}\n", WORKLOAD_DURATION);
+
+    let output_root = PathBuf::from("./Memoryanalysis/bottleneck_validation");
+    if output_root.exists() {
+        std::fs::remove_dir_all(&output_root)?;
+    }
+
+    let mut results = Vec::new();
+    for workload in SkewedWorkload::ALL {
+        let output_dir = output_root.join(workload.name());
+        std::fs::create_dir_all(&output_dir)?;
+        seed::record(&output_dir, seed)?;
+
+        println!("🔄 Running {} workload...", workload.name());
+        let result = profile_workload(workload, threads, seed, &output_dir)?;
+        println!(
+            "   {} Primary bottleneck: {:?} (expected {})",
+            if result.matches() { "✅" } else { "❌" },
+            result.reported_bottleneck,
+            workload.expected_bottleneck()
+        );
+        println!(
+            "   📈 CPU efficiency: {:.1}%, Memory efficiency: {:.1}%, Operations: {}",
+            result.cpu_efficiency, result.memory_efficiency, result.operations
+        );
+        results.push(result);
+    }
+
+    println!("\n📊 Bottleneck Classification Summary:");
+    for result in &results {
+        println!(
+            "   {} {:<18} → {:?}",
+            if result.matches() { "✅" } else { "❌" },
+            result.workload.name(),
+            result.reported_bottleneck
+        );
+    }
+
+    let mismatches: Vec<_> = results.iter().filter(|r| !r.matches()).collect();
+    if mismatches.is_empty() {
+        println!("   ✅ SUCCESS: Every workload was classified correctly");
+        Ok(())
+    } else {
+        println!(
+            "   ❌ FAILED: {} workload(s) misclassified",
+            mismatches.len()
+        );
+        Err(format!(
+            "misclassified workloads: {}",
+            mismatches
+                .iter()
+                .map(|r| format!(
+                    "{} reported as {:?}",
+                    r.workload.name(),
+                    r.reported_bottleneck
+                ))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+        .into())
+    }
+}
+
+/// Run `workload` on `threads` workers inside an integrated profiling session
+fn profile_workload(
+    workload: SkewedWorkload,
+    threads: usize,
+    seed: u64,
+    output_dir: &Path,
+) -> Result<ValidationResult, Box<dyn std::error::Error>> {
+    let mut session = IntegratedProfilingSession::new(output_dir)?;
+    session.start_profiling()?;
+
+    let operations = Arc::new(AtomicUsize::new(0));
+    let deadline = Instant::now() + WORKLOAD_DURATION;
+
+    let handles: Vec<_> = (0..threads)
+        .map(|thread_idx| {
+            let output_dir = output_dir.to_path_buf();
+            let operations = Arc::clone(&operations);
+
+            thread::spawn(move || -> Result<(), String> {
+                run_skewed_worker(
+                    workload,
+                    thread_idx,
+                    seed,
+                    &output_dir,
+                    deadline,
+                    &operations,
+                )
+            })
+        })
+        .collect();
+
+    for (idx, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(format!("thread {} failed: {}", idx, e).into()),
+            Err(_) => return Err(format!("thread {} panicked", idx).into()),
+        }
+    }
+
+    let analysis = session.stop_profiling_and_analyze()?;
+
+    Ok(ValidationResult {
+        workload,
+        reported_bottleneck: analysis.performance_insights.primary_bottleneck,
+        cpu_efficiency: analysis.performance_insights.cpu_efficiency_score as f64,
+        memory_efficiency: analysis.performance_insights.memory_efficiency_score as f64,
+        operations: operations.load(Ordering::Relaxed),
+    })
+}
+
+/// Worker that keeps a single resource saturated until `deadline`
+fn run_skewed_worker(
+    workload: SkewedWorkload,
+    thread_idx: usize,
+    seed: u64,
+    output_dir: &Path,
+    deadline: Instant,
+    operations: &AtomicUsize,
+) -> Result<(), String> {
+    init_thread_tracker(output_dir, Some(SamplingConfig::demo()))
+        .map_err(|e| format!("Failed to init tracker: {}", e))?;
+
+    let local_ops = match workload {
+        SkewedWorkload::Compute => run_compute_loop(thread_idx, deadline),
+        SkewedWorkload::AllocationChurn => run_allocation_churn(thread_idx, seed, deadline)?,
+        SkewedWorkload::FsyncIo => run_fsync_io(thread_idx, output_dir, deadline)?,
+    };
+
+    finalize_thread_tracker().map_err(|e| format!("Failed to finalize tracker: {}", e))?;
+    operations.fetch_add(local_ops, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Pure arithmetic with no allocations or I/O
+fn run_compute_loop(thread_idx: usize, deadline: Instant) -> usize {
+    let mut ops = 0;
+    let mut state = thread_idx as u64 + 1;
+    while Instant::now() < deadline {
+        for _ in 0..100_000 {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            state ^= state >> 33;
+        }
+        ops += 1;
+    }
+    std::hint::black_box(state);
+    ops
+}
+
+/// Tracked allocate/free cycles over a large, constantly replaced live set
+fn run_allocation_churn(thread_idx: usize, seed: u64, deadline: Instant) -> Result<usize, String> {
+    const LIVE_SET: usize = 256;
+
+    let mut rng = seed::stream_rng(seed, thread_idx);
+    let mut live: Vec<Vec<u8>> = Vec::with_capacity(LIVE_SET);
+    let mut ops = 0;
+    while Instant::now() < deadline {
+        let alloc_size = 4096 << rng.random_range(0..6); // 4KB - 128KB
+        let call_stack = [0x1000 + thread_idx, 0x2000 + ops % 64];
+
+        let data = vec![(ops % 256) as u8; alloc_size];
+        track_allocation_lockfree(data.as_ptr() as usize, alloc_size, &call_stack)
+            .map_err(|e| format!("Failed to track allocation: {}", e))?;
+
+        if live.len() == LIVE_SET {
+            let evicted = live.swap_remove(rng.random_range(0..LIVE_SET));
+            track_deallocation_lockfree(evicted.as_ptr() as usize, &call_stack)
+                .map_err(|e| format!("Failed to track deallocation: {}", e))?;
+        }
+        live.push(data);
+        ops += 1;
+    }
+
+    for data in live {
+        track_deallocation_lockfree(data.as_ptr() as usize, &[0x1000 + thread_idx])
+            .map_err(|e| format!("Failed to track deallocation: {}", e))?;
+    }
+    Ok(ops)
+}
+
+/// Small writes each forced to disk with `sync_all`
+///
+/// The file lives in `output_dir` rather than the system temp directory, which
+/// may be tmpfs where `sync_all` never reaches a disk, and is removed on drop.
+fn run_fsync_io(thread_idx: usize, output_dir: &Path, deadline: Instant) -> Result<usize, String> {
+    let mut file =
+        NamedTempFile::new_in(output_dir).map_err(|e| format!("Failed to create file: {}", e))?;
+
+    let block = [thread_idx as u8; 512];
+    let mut ops = 0;
+    while Instant::now() < deadline {
+        let file = file.as_file_mut();
+        file.write_all(&block)
+            .and_then(|()| file.sync_all())
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+        ops += 1;
+    }
+
+    Ok(ops)
+}
@@ -0,0 +1,258 @@
+//! Hottest Call Stack Ranking with a Skewed Distribution
+//!
+//! This scenario drives allocations through a handful of distinct call paths
+//! with a designed Zipf-like frequency and byte distribution, then checks the
+//! `LockfreeAggregator` result:
+//! 1. `hottest_call_stacks` ranks the paths in the designed order
+//! 2. Each ranked entry carries the exact allocation count and byte total
+//! 3. `summary.unique_call_stacks` equals the number of paths used
+
+use memscope_rs::lockfree::aggregator::LockfreeAggregator;
+use memscope_rs::lockfree::analysis::HotCallStack;
+use memscope_rs::lockfree::tracker::{
+    finalize_thread_tracker, init_thread_tracker, track_allocation_lockfree, SamplingConfig,
+};
+use memscope_stress_test::seed;
+use rand::Rng;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+
+/// Worker threads sharing every call path
+const THREADS: usize = 4;
+
+/// Allocations issued on the hottest path; path `k` (1-based) gets `1 / k` of it
+const HOTTEST_PATH_ALLOCATIONS: usize = 1200;
+
+/// Allocation size on the hottest path; path `k` (1-based) uses `1 / 2^(k-1)` of it
+const HOTTEST_PATH_SIZE: usize = 4096;
+
+/// A synthetic call path with its designed share of the workload
+struct CallPath {
+    name: &'static str,
+    frames: [usize; 3],
+    allocations: usize,
+    allocation_size: usize,
+}
+
+impl CallPath {
+    fn total_bytes(&self) -> usize {
+        self.allocations * self.allocation_size
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔥 Hottest Call Stack Ranking with a Skewed Distribution");
+    println!("=======================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let paths = designed_call_paths();
+    println!("   🧵 Threads: {}, Call paths: {}", THREADS, paths.len());
+    for (rank, path) in paths.iter().enumerate() {
+        println!(
+            "   #{} {:<22} {:>5} allocs x {:>5} bytes = {:>8} bytes",
+            rank + 1,
+            path.name,
+            path.allocations,
+            path.allocation_size,
+            path.total_bytes()
+        );
+    }
+
+    let output_dir = PathBuf::from("./Memoryanalysis/call_stack_ranking");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let paths = Arc::new(paths);
+    let handles: Vec<_> = (0..THREADS)
+        .map(|thread_idx| {
+            let output_dir = output_dir.clone();
+            let paths = Arc::clone(&paths);
+
+            thread::spawn(move || -> Result<(), String> {
+                run_call_path_worker(thread_idx, seed, &output_dir, &paths)
+            })
+        })
+        .collect();
+
+    for (idx, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => return Err(format!("thread {} failed: {}", idx, e).into()),
+            Err(_) => return Err(format!("thread {} panicked", idx).into()),
+        }
+    }
+
+    let aggregator = LockfreeAggregator::new(output_dir.clone());
+    let analysis = aggregator.aggregate_all_threads()?;
+    let json_path = output_dir.join("call_stack_ranking.json");
+    aggregator.export_analysis(&analysis, &json_path)?;
+
+    println!("\n📊 Reported hottest call stacks:");
+    for (rank, stack) in analysis.hottest_call_stacks.iter().enumerate() {
+        println!(
+            "   #{} hash {:016x}: {} allocs, {} bytes, impact {}",
+            rank + 1,
+            stack.call_stack_hash,
+            stack.total_frequency,
+            stack.total_size,
+            stack.impact_score
+        );
+    }
+
+    verify_ranking(
+        &paths,
+        &analysis.hottest_call_stacks,
+        analysis.summary.unique_call_stacks,
+    )
+}
+
+/// Five call paths whose counts follow `1 / rank` and whose sizes halve with
+/// each rank, so frequency, bytes and any product of the two agree on order
+fn designed_call_paths() -> Vec<CallPath> {
+    let names = [
+        "request_parser",
+        "response_serializer",
+        "session_cache",
+        "audit_logger",
+        "metrics_flush",
+    ];
+
+    names
+        .iter()
+        .enumerate()
+        .map(|(idx, &name)| {
+            let rank = idx + 1;
+            CallPath {
+                name,
+                frames: [0x1000, 0x2000 + rank * 0x10, 0x3000 + rank * 0x100],
+                // Round down to a multiple of THREADS so every worker gets an equal share
+                allocations: HOTTEST_PATH_ALLOCATIONS / rank / THREADS * THREADS,
+                allocation_size: HOTTEST_PATH_SIZE >> idx,
+            }
+        })
+        .collect()
+}
+
+/// Issue this thread's share of every path in a seeded random order, so no
+/// single path runs in one uninterrupted burst
+fn run_call_path_worker(
+    thread_idx: usize,
+    seed: u64,
+    output_dir: &Path,
+    paths: &[CallPath],
+) -> Result<(), String> {
+    // Exact counts are checked, so every allocation must be recorded
+    init_thread_tracker(output_dir, Some(SamplingConfig::high_precision()))
+        .map_err(|e| format!("Failed to init tracker: {}", e))?;
+
+    let mut rng = seed::stream_rng(seed, thread_idx);
+    let mut remaining: Vec<usize> = paths.iter().map(|p| p.allocations / THREADS).collect();
+    let mut total_remaining: usize = remaining.iter().sum();
+    let mut live = Vec::with_capacity(total_remaining);
+
+    while total_remaining > 0 {
+        // Pick the next path weighted by how many allocations it has left
+        let mut pick = rng.random_range(0..total_remaining);
+        let path_idx = remaining
+            .iter()
+            .position(|&left| {
+                if pick < left {
+                    true
+                } else {
+                    pick -= left;
+                    false
+                }
+            })
+            .ok_or("path selection out of range")?;
+        remaining[path_idx] -= 1;
+        total_remaining -= 1;
+
+        let path = &paths[path_idx];
+        let data = vec![thread_idx as u8; path.allocation_size];
+        track_allocation_lockfree(data.as_ptr() as usize, path.allocation_size, &path.frames)
+            .map_err(|e| format!("Failed to track allocation: {}", e))?;
+        // Keep buffers alive so addresses are never reused mid-run
+        live.push(data);
+    }
+
+    finalize_thread_tracker().map_err(|e| format!("Failed to finalize tracker: {}", e))?;
+    drop(live);
+
+    Ok(())
+}
+
+/// Compare the reported ranking against the designed distribution
+fn verify_ranking(
+    paths: &[CallPath],
+    hottest: &[HotCallStack],
+    unique_call_stacks: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("\n🔍 Verifying Call Stack Ranking:");
+
+    let mut failures = Vec::new();
+
+    if unique_call_stacks == paths.len() {
+        println!("   ✅ Unique call stacks: {}", unique_call_stacks);
+    } else {
+        println!(
+            "   ❌ Unique call stacks: expected {}, got {}",
+            paths.len(),
+            unique_call_stacks
+        );
+        failures.push(format!(
+            "unique_call_stacks {} != {}",
+            unique_call_stacks,
+            paths.len()
+        ));
+    }
+
+    if hottest.len() < paths.len() {
+        failures.push(format!(
+            "only {} hottest call stacks reported for {} paths",
+            hottest.len(),
+            paths.len()
+        ));
+    }
+
+    for (rank, (path, stack)) in paths.iter().zip(hottest).enumerate() {
+        let count_ok = stack.total_frequency == path.allocations as u64;
+        let bytes_ok = stack.total_size == path.total_bytes();
+
+        println!(
+            "   {} #{} {:<22} count {}/{} bytes {}/{}",
+            if count_ok && bytes_ok { "✅" } else { "❌" },
+            rank + 1,
+            path.name,
+            stack.total_frequency,
+            path.allocations,
+            stack.total_size,
+            path.total_bytes()
+        );
+
+        if !count_ok || !bytes_ok {
+            failures.push(format!(
+                "rank {} expected {} ({} allocs, {} bytes), got {} allocs, {} bytes",
+                rank + 1,
+                path.name,
+                path.allocations,
+                path.total_bytes(),
+                stack.total_frequency,
+                stack.total_size
+            ));
+        }
+    }
+
+    if failures.is_empty() {
+        println!("   ✅ SUCCESS: hottest_call_stacks matches the designed distribution");
+        Ok(())
+    } else {
+        println!("   ❌ FAILED: {} ranking mismatch(es)", failures.len());
+        Err(failures.join("; ").into())
+    }
+}
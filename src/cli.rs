@@ -0,0 +1,24 @@
+//! Minimal command-line helpers shared by the scenario binaries
+
+/// Value of `--name <value>` or `--name=<value>` from the process arguments
+pub fn flag_value(name: &str) -> Option<String> {
+    flag_value_in(std::env::args().skip(1), name)
+}
+
+/// Value of `--name <value>` or `--name=<value>` from `args`
+pub fn flag_value_in<I>(args: I, name: &str) -> Option<String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let prefix = format!("{name}=");
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
@@ -1,74 +1,132 @@
 use std::thread;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use memscope_rs::{init, track_var};
 use memscope_rs::export::fixed_hybrid_template::{FixedHybridTemplate, RenderMode};
+use memscope_stress_test::seed;
+use memscope_stress_test::html_report::{self, HtmlReport};
+use memscope_stress_test::{js_syntax, placeholder_values, report_data, report_roundtrip};
+use memscope_stress_test::resource_sampler::{self, ResourceSample, ResourceSampler};
+use memscope_stress_test::task_registry::{TaskId, TaskRegistry, TaskTotals};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Track a Vec with `track_var!`, attribute it to a task and observe its drop
+///
+/// The plain form records the Vec's buffer, which is all the heap it owns
+/// because its elements must be `Copy`, so the task's memory is compared
+/// exactly. The `nested` form is for elements owning heap of their own
+/// (`Vec<String>`, `Vec<Vec<T>>`): only the outer buffer is recorded and the
+/// task joins `$outer_only`, whose memory is left out of the comparison.
+macro_rules! track_task_var {
+    ($tasks:expr, $task:expr, $var:ident) => {
+        track_var!($var);
+        $tasks.record_variable($task, stringify!($var), $var.as_ptr() as usize, flat_buffer_size(&$var));
+        // Declared after the variable, so it leaves scope in the same block
+        let _drop_guard = $tasks.drop_guard($task, $var.as_ptr() as usize);
+    };
+    ($tasks:expr, $task:expr, $var:ident, nested in $outer_only:expr) => {
+        track_var!($var);
+        $tasks.record_variable($task, stringify!($var), $var.as_ptr() as usize, std::mem::size_of_val($var.as_slice()));
+        $outer_only.insert($task);
+        let _drop_guard = $tasks.drop_guard($task, $var.as_ptr() as usize);
+    };
+}
+
+/// Heap bytes of a buffer whose elements own no heap memory themselves
+fn flat_buffer_size<T: Copy>(buffer: &[T]) -> usize {
+    std::mem::size_of_val(buffer)
+}
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🔍 Deep Inspector Real Data Verification Test");
-    println!("Testing different variable types and sizes...\n");
+    println!("Testing different variable types and sizes...");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    println!();
+    let mut rng = seed::rng(seed);
     
     init();
+    resource_sampler::register_current_thread();
+    // Every tracked variable is attributed to the logical task that created it
+    let tasks = Arc::new(TaskRegistry::new());
+    // Sample CPU, I/O and tracked memory in the background for the dashboard charts
+    let sampler = ResourceSampler::start(Duration::from_millis(50))?;
 
     // Test Case 1:Large memory buffers
+    let media_task = tasks.start_task("media_buffers");
     let large_image_buffer = vec![0u8; 1024 * 512]; // 512KB
-    track_var!(large_image_buffer);
+    track_task_var!(tasks, media_task, large_image_buffer);
     
     let video_frame_buffer = vec![0u32; 1920 * 1080]; // ~8MB
-    track_var!(video_frame_buffer);
+    track_task_var!(tasks, media_task, video_frame_buffer);
     
     // Test Case 2: Network buffers
+    let network_task = tasks.start_task("network_buffers");
     let tcp_recv_buffer = vec![0u8; 8192]; // 8KB
-    track_var!(tcp_recv_buffer);
+    track_task_var!(tasks, network_task, tcp_recv_buffer);
     
     let http_response_cache = vec![0u8; 65536]; // 64KB
-    track_var!(http_response_cache);
+    track_task_var!(tasks, network_task, http_response_cache);
     
     // Test Case 3: 计算相关数据 (Computation data)
+    let compute_task = tasks.start_task("computation_data");
     let matrix_data = vec![0f64; 1000 * 1000]; // 8MB matrix
-    track_var!(matrix_data);
+    track_task_var!(tasks, compute_task, matrix_data);
     
     let fft_coefficients = vec![0f32; 4096]; // 16KB
-    track_var!(fft_coefficients);
+    track_task_var!(tasks, compute_task, fft_coefficients);
     
     // Test Case 4: 多线程场景 (Multi-threading scenarios)
     let shared_counter = Arc::new(Mutex::new(0));
     let mut handles = vec![];
+
+    // Workload cases are assigned to threads in seeded order, each held for a seeded time
+    let mut workload_cases: Vec<usize> = (0..5).collect();
+    workload_cases.shuffle(&mut rng);
     
-    for thread_id in 0..5 {
+    for workload_case in workload_cases {
         let counter = Arc::clone(&shared_counter);
+        let tasks = Arc::clone(&tasks);
+        let hold_time = Duration::from_millis(rng.random_range(50..250));
         let handle = thread::spawn(move || {
+            resource_sampler::register_current_thread();
             // local variables in each thread
-            match thread_id {
+            match workload_case {
                 0 => {
+                    let task = tasks.start_task("database_worker");
                     let database_cache = vec![0u8; 256 * 1024]; // 256KB
-                    track_var!(database_cache);
-                    thread::sleep(Duration::from_millis(100));
+                    track_task_var!(tasks, task, database_cache);
+                    thread::sleep(hold_time);
                 },
                 1 => {
+                    let task = tasks.start_task("crypto_worker");
                     let crypto_key_buffer = vec![0u8; 4096]; // 4KB
-                    track_var!(crypto_key_buffer);
+                    track_task_var!(tasks, task, crypto_key_buffer);
                     let signature_data = vec![0u8; 2048]; // 2KB
-                    track_var!(signature_data);
-                    thread::sleep(Duration::from_millis(150));
+                    track_task_var!(tasks, task, signature_data);
+                    thread::sleep(hold_time);
                 },
                 2 => {
+                    let task = tasks.start_task("audio_worker");
                     let audio_sample_buffer = vec![0i16; 44100]; // ~88KB
-                    track_var!(audio_sample_buffer);
-                    thread::sleep(Duration::from_millis(80));
+                    track_task_var!(tasks, task, audio_sample_buffer);
+                    thread::sleep(hold_time);
                 },
                 3 => {
+                    let task = tasks.start_task("compression_worker");
                     let file_read_buffer = vec![0u8; 128 * 1024]; // 128KB
-                    track_var!(file_read_buffer);
+                    track_task_var!(tasks, task, file_read_buffer);
                     let compression_workspace = vec![0u8; 64 * 1024]; // 64KB
-                    track_var!(compression_workspace);
-                    thread::sleep(Duration::from_millis(120));
+                    track_task_var!(tasks, task, compression_workspace);
+                    thread::sleep(hold_time);
                 },
                 4 => {
+                    let task = tasks.start_task("gpu_worker");
                     let gpu_texture_data = vec![0u8; 2048 * 2048 * 4]; // 16MB
-                    track_var!(gpu_texture_data);
-                    thread::sleep(Duration::from_millis(200));
+                    track_task_var!(tasks, task, gpu_texture_data);
+                    thread::sleep(hold_time);
                 },
                 _ => {}
             }
@@ -82,10 +140,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Test Case 5: Dynamic allocation/deallocation
     println!("Creating dynamic allocations...");
-    for i in 0..3 {
-        let dynamic_buffer = vec![0u8; (i + 1) * 32 * 1024]; // 32KB, 64KB, 96KB
-        track_var!(dynamic_buffer);
-        thread::sleep(Duration::from_millis(50));
+    for round in 0..3 {
+        let task = tasks.start_task(&format!("dynamic_allocation_{}", round));
+        let dynamic_buffer = vec![0u8; rng.random_range(16..=128) * 1024]; // 16KB - 128KB
+        track_task_var!(tasks, task, dynamic_buffer);
+        thread::sleep(Duration::from_millis(rng.random_range(20..80)));
         // Buffer will be dropped here
     }
 
@@ -95,31 +154,36 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Test Case 6: Special data types
+    let special_task = tasks.start_task("special_types");
+    // Tasks whose recorded memory covers only the outer buffer of their Vecs
+    let mut outer_buffer_only = HashSet::new();
     let string_collection: Vec<String> = (0..1000)
         .map(|i| format!("data_entry_{}", i))
         .collect();
-    track_var!(string_collection);
+    track_task_var!(tasks, special_task, string_collection, nested in outer_buffer_only);
     
     let nested_structure = vec![vec![0i32; 100]; 50]; 
-    track_var!(nested_structure);
+    track_task_var!(tasks, special_task, nested_structure, nested in outer_buffer_only);
     
+    let samples = sampler.stop()?;
+    println!("📈 Resource samples taken: {}", samples.len());
+
     println!("\n📊 Generating comprehensive Deep Inspector report...");
     
     // Deep Inspector
-    generate_deep_inspector_verification_report()?;
+    generate_deep_inspector_verification_report(seed, &tasks, &outer_buffer_only, &samples)?;
     
     println!("\n🎯 Verification checklist:");
     println!("   □ Variable names show real identifiers (not 'Vec<u8> allocated')");
     println!("   □ Memory sizes match actual allocations");  
     println!("   □ Timeline shows track_var! registration events");
-    println!("   □ No JavaScript syntax errors in browser console");
     println!("   □ Deep Inspector pages navigate properly");
-    println!("   □ Memory attribution percentages are calculated (not hardcoded 15%)");
+    seed::announce(seed);
     
     Ok(())
 }
 
-fn generate_deep_inspector_verification_report() -> Result<(), Box<dyn std::error::Error>> {
+fn generate_deep_inspector_verification_report(seed: u64, tasks: &TaskRegistry, outer_buffer_only: &HashSet<TaskId>, samples: &[ResourceSample]) -> Result<(), Box<dyn std::error::Error>> {
     use std::collections::HashMap;
     
     println!("\n📊 Generating Deep Inspector verification report...");
@@ -129,6 +193,23 @@ fn generate_deep_inspector_verification_report() -> Result<(), Box<dyn std::erro
     println!("🔢 Variables tracked: {}", real_variables.len());
     
     let total_memory: u64 = real_variables.values().map(|v| v.memory_usage).sum();
+
+    // Resolve each task's thread through the variables it tracked: by heap
+    // address first, then by name when the name is unique in the registry
+    let mut threads_by_name: HashMap<&str, Vec<usize>> = HashMap::new();
+    for var_info in real_variables.values() {
+        threads_by_name.entry(var_info.var_name.as_str()).or_default().push(var_info.thread_id);
+    }
+    let thread_task_mapping = tasks.thread_task_mapping(|variable| {
+        real_variables
+            .get(&variable.addr)
+            .map(|var_info| var_info.thread_id)
+            .or_else(|| match threads_by_name.get(variable.name.as_str()) {
+                Some(threads) if threads.len() == 1 => Some(threads[0]),
+                _ => None,
+            })
+    });
+    println!("🧵 Threads with tasks: {}", thread_task_mapping.len());
     println!("💾 Total memory tracked: {:.2} MB", total_memory as f64 / (1024.0 * 1024.0));
     
     let mut lockfree_analysis = memscope_rs::lockfree::analysis::LockfreeAnalysis::new();
@@ -136,17 +217,20 @@ fn generate_deep_inspector_verification_report() -> Result<(), Box<dyn std::erro
     lockfree_analysis.summary.total_allocations = real_variables.len() as u64;
     
     let variable_details: HashMap<String, memscope_rs::export::fixed_hybrid_template::VariableDetail> = 
-        real_variables.into_iter().map(|(addr, var_info)| {
+        real_variables.iter().map(|(&addr, var_info)| {
             (
                 format!("{}_{:x}", var_info.var_name, addr),
                 memscope_rs::export::fixed_hybrid_template::VariableDetail {
                     name: var_info.var_name.clone(),
                     type_info: var_info.type_name.clone(),
                     thread_id: var_info.thread_id,
-                    task_id: Some(var_info.thread_id * 100 + (addr % 100)),
+                    task_id: tasks.task_for_address(addr).or_else(|| tasks.task_for_name(&var_info.var_name)),
                     allocation_count: 1,
                     memory_usage: var_info.memory_usage,
-                    lifecycle_stage: memscope_rs::export::fixed_hybrid_template::LifecycleStage::Active,
+                    lifecycle_stage: match tasks.variable_at(addr) {
+                        Some(variable) if variable.dropped => memscope_rs::export::fixed_hybrid_template::LifecycleStage::Deallocated,
+                        _ => memscope_rs::export::fixed_hybrid_template::LifecycleStage::Active,
+                    },
                 },
             )
         }).collect();
@@ -154,17 +238,9 @@ fn generate_deep_inspector_verification_report() -> Result<(), Box<dyn std::erro
     let hybrid_data = memscope_rs::export::fixed_hybrid_template::HybridAnalysisData {
         variable_registry: variable_details.clone(),
         lockfree_analysis: Some(lockfree_analysis),
-        thread_task_mapping: HashMap::new(),
+        thread_task_mapping: thread_task_mapping.clone(),
         visualization_config: Default::default(),
-        performance_metrics: memscope_rs::export::fixed_hybrid_template::PerformanceTimeSeries {
-            cpu_usage: Vec::new(),
-            memory_usage: Vec::new(),
-            io_operations: Vec::new(),
-            network_bytes: Vec::new(),
-            timestamps: Vec::new(),
-            thread_cpu_breakdown: HashMap::new(),
-            thread_memory_breakdown: HashMap::new(),
-        },
+        performance_metrics: resource_sampler::performance_time_series(samples),
     };
     
     
@@ -173,7 +249,8 @@ fn generate_deep_inspector_verification_report() -> Result<(), Box<dyn std::erro
         .with_variable_details(true)
         .with_enhanced_insights(true);
     
-    let html_content = template.generate_hybrid_dashboard(&hybrid_data)?;
+    let mut html_content = template.generate_hybrid_dashboard(&hybrid_data)?;
+    html_content.push_str(&seed::html_comment(seed));
     std::fs::write("deep_inspector_real_data_verification.html", html_content)?;
     
     println!("✅ Deep Inspector verification report generated!");
@@ -181,14 +258,61 @@ fn generate_deep_inspector_verification_report() -> Result<(), Box<dyn std::erro
     
     // 验证报告内容
     verify_report_content(&variable_details)?;
+    js_syntax::verify_report(std::path::Path::new("deep_inspector_real_data_verification.html"))?;
+    verify_thread_task_mapping(&thread_task_mapping)?;
+    verify_task_totals(&variable_details, &tasks.totals(), outer_buffer_only)?;
+    verify_lifecycle_stages(&variable_details)?;
+    verify_variable_round_trip(&variable_details)?;
+    verify_performance_charts(&hybrid_data.performance_metrics, samples.len())?;
+    verify_no_placeholder_values(&template, &hybrid_data)?;
     
     Ok(())
 }
 
+/// Sections the deep inspector dashboard must contain, by element id
+const REQUIRED_SECTION_IDS: [&str; 4] = ["variables-container", "threadActivityGrid", "theme-toggle", "variable-modal"];
+
+/// Check the rendered DOM: title and heading, required sections, and one
+/// variable card per tracked variable on the right thread
 fn verify_report_content(variable_details: &HashMap<String, memscope_rs::export::fixed_hybrid_template::VariableDetail>) -> Result<(), Box<dyn std::error::Error>> {
     use std::fs;
     
     let html_content = fs::read_to_string("deep_inspector_real_data_verification.html")?;
+    let report = HtmlReport::parse(&html_content);
+    let mut problems = Vec::new();
+
+    let parse_errors = report.parse_errors();
+    if !parse_errors.is_empty() {
+        println!("⚠️  HTML parser recovered from {} error(s): {}", parse_errors.len(), parse_errors.join(", "));
+    }
+
+    // title and main heading
+    let title = report.title().unwrap_or_default();
+    let h1 = report.headings("h1");
+    if !title.is_empty() && h1.first() == Some(&title) {
+        println!("✅ title and heading: {}", title);
+    } else {
+        problems.push(format!("<title> '{}' does not match <h1> {:?}", title, h1));
+    }
+
+    for id in report.missing_ids(&REQUIRED_SECTION_IDS) {
+        problems.push(format!("required section #{} is missing", id));
+    }
+
+    // variable cards rendered into the static DOM
+    let name_selector = scraper::Selector::parse(".variable-name").unwrap();
+    let mut cards = Vec::new();
+    for card in report.select("#variables-container .variable-card")? {
+        let name = card
+            .select(&name_selector)
+            .next()
+            .map(|e| html_report::element_text(&e))
+            .unwrap_or_default();
+        // names are prefixed with a status emoji
+        let name = name.split_once(' ').map_or(name.clone(), |(_, n)| n.to_string());
+        let thread = card.value().attr("data-thread").and_then(|t| t.parse::<usize>().ok());
+        cards.push((name, thread));
+    }
     
     // check real allocations
     let real_variable_names = [
@@ -206,28 +330,303 @@ fn verify_report_content(variable_details: &HashMap<String, memscope_rs::export:
     
     let mut real_data_count = 0;
     for var_name in &real_variable_names {
-        if html_content.contains(var_name) {
+        if cards.iter().any(|(name, _)| name == var_name) {
             real_data_count += 1;
             println!("✅ real var name: {}", var_name);
+        } else {
+            problems.push(format!("no variable card for {}", var_name));
         }
     }
 
+    for (name, thread) in &cards {
+        let known = variable_details.values().any(|d| &d.name == name && Some(d.thread_id) == *thread);
+        if !known {
+            problems.push(format!("card '{}' on thread {:?} matches no tracked variable", name, thread));
+        }
+    }
+    if cards.len() != variable_details.len() {
+        problems.push(format!("{} variable cards for {} tracked variables", cards.len(), variable_details.len()));
+    }
+
     println!("📊 real variable data statistics:");
     println!("   • real variable names: {}/{}", real_data_count, real_variable_names.len());
+    println!("   • variable cards: {}", cards.len());
     println!("   • total tracked variables: {}", variable_details.len());
 
-    if real_data_count == real_variable_names.len() {
-        println!("✅ All real variable names are correctly displayed");
-    } else {
-        println!("❌ Some variable names are missing or incorrect");
-    }
-    if html_content.contains("track_var!(") {
+    // the inspector pages are rendered by script on demand
+    let scripts = report.script_source();
+    if scripts.contains("track_var!(") {
         println!("✅ track_var! call is correctly displayed");
     }
     
-    if html_content.contains("Variable Tracking Timeline") {
+    if scripts.contains("Variable Tracking Timeline") {
         println!("✅ Interface title has been updated to the real description");
     }
-    
+
+    if problems.is_empty() {
+        println!("✅ All real variable names are correctly displayed");
+        Ok(())
+    } else {
+        for problem in &problems {
+            println!("❌ {}", problem);
+        }
+        Err(format!("{} structural problem(s) in the dashboard", problems.len()).into())
+    }
+}
+
+/// Check that the dashboard lists every mapped thread and each of its tasks
+fn verify_thread_task_mapping(thread_task_mapping: &HashMap<usize, Vec<TaskId>>) -> Result<(), Box<dyn std::error::Error>> {
+    let html_content = std::fs::read_to_string("deep_inspector_real_data_verification.html")?;
+
+    let rendered_threads = report_data::embedded_array(&html_content, report_data::DASHBOARD_DATA, "threads")?;
+    let rendered_tasks = report_data::embedded_array(&html_content, report_data::DASHBOARD_DATA, "tasks")?;
+
+    let mut thread_ids: Vec<_> = thread_task_mapping.keys().copied().collect();
+    thread_ids.sort_unstable();
+
+    let mut missing = 0;
+    println!("🧵 thread/task mapping in dashboard:");
+    for thread_id in thread_ids {
+        let thread_shown = rendered_threads.iter().any(|t| t["id"].as_u64() == Some(thread_id as u64));
+        let task_ids = &thread_task_mapping[&thread_id];
+        let tasks_shown = task_ids.iter().filter(|&&task_id| {
+            rendered_tasks.iter().any(|t| {
+                t["id"].as_u64() == Some(task_id as u64) && t["thread"].as_u64() == Some(thread_id as u64)
+            })
+        }).count();
+
+        if thread_shown && tasks_shown == task_ids.len() {
+            println!("✅ thread {}: tasks {:?}", thread_id, task_ids);
+        } else {
+            missing += 1;
+            println!("❌ thread {} (shown: {}): {}/{} tasks shown of {:?}", thread_id, thread_shown, tasks_shown, task_ids.len(), task_ids);
+        }
+    }
+
+    if missing == 0 {
+        println!("✅ All {} threads and their tasks are displayed", thread_task_mapping.len());
+        Ok(())
+    } else {
+        println!("❌ {} of {} threads are missing or incomplete in the dashboard", missing, thread_task_mapping.len());
+        Err(format!("{} threads missing or incomplete in dashboard", missing).into())
+    }
+}
+
+/// Check that per-task allocation counts and memory add up to the task registry,
+/// both in the variable details fed to the template and in the rendered dashboard
+///
+/// Memory is compared exactly only for tasks whose variables are flat buffers;
+/// for tasks in `outer_buffer_only` the registry misses the heap owned by the
+/// elements, so only their allocation counts are compared.
+fn verify_task_totals(
+    variable_details: &HashMap<String, memscope_rs::export::fixed_hybrid_template::VariableDetail>,
+    registry_totals: &HashMap<TaskId, TaskTotals>,
+    outer_buffer_only: &HashSet<TaskId>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let html_content = std::fs::read_to_string("deep_inspector_real_data_verification.html")?;
+    let rendered_tasks = report_data::embedded_array(&html_content, report_data::DASHBOARD_DATA, "tasks")?;
+
+    let mut detail_totals: HashMap<TaskId, TaskTotals> = HashMap::new();
+    let mut unattributed = 0;
+    for detail in variable_details.values() {
+        match detail.task_id {
+            Some(task_id) => {
+                let totals = detail_totals.entry(task_id).or_default();
+                totals.allocation_count += detail.allocation_count;
+                totals.memory_usage += detail.memory_usage;
+            }
+            None => unattributed += 1,
+        }
+    }
+
+    let mut task_ids: Vec<_> = registry_totals.keys().copied().collect();
+    task_ids.sort_unstable();
+
+    let mut failures = Vec::new();
+    if unattributed > 0 {
+        failures.push(format!("{} variables have no task", unattributed));
+    }
+
+    println!("📋 per-task totals (registry vs dashboard):");
+    for task_id in task_ids {
+        let expected = registry_totals[&task_id];
+        let detail = detail_totals.get(&task_id).copied().unwrap_or_default();
+        // The dashboard shows task memory in whole KB
+        let expected_kb = (expected.memory_usage as f64 / 1024.0).round() as i64;
+        let rendered = rendered_tasks.iter().find(|t| t["id"].as_u64() == Some(task_id as u64));
+        let exact_memory = !outer_buffer_only.contains(&task_id);
+        let rendered_ok = rendered.is_some_and(|t| {
+            t["variables"].as_u64() == Some(expected.allocation_count)
+                && (!exact_memory || t["memory"].as_i64().is_some_and(|kb| (kb - expected_kb).abs() <= 1))
+        });
+        let detail_ok = if exact_memory {
+            detail == expected
+        } else {
+            detail.allocation_count == expected.allocation_count
+        };
+
+        if detail_ok && rendered_ok {
+            println!("✅ task {}: {} allocations, {} bytes", task_id, expected.allocation_count, expected.memory_usage);
+        } else {
+            println!("❌ task {}: registry {:?}, details {:?}, dashboard {}",
+                task_id, expected, detail, rendered.map(|t| t.to_string()).unwrap_or_else(|| "missing".to_string()));
+            failures.push(format!("task {} totals do not match the registry", task_id));
+        }
+    }
+
+    if failures.is_empty() {
+        println!("✅ Task totals match the registry for all {} tasks", registry_totals.len());
+        Ok(())
+    } else {
+        println!("❌ {} task total mismatch(es)", failures.len());
+        Err(failures.join("; ").into())
+    }
+}
+
+/// Check that the dashboard shows dropped variables as no longer active
+fn verify_lifecycle_stages(
+    variable_details: &HashMap<String, memscope_rs::export::fixed_hybrid_template::VariableDetail>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use memscope_rs::export::fixed_hybrid_template::LifecycleStage;
+
+    let html_content = std::fs::read_to_string("deep_inspector_real_data_verification.html")?;
+    let rendered_variables = report_data::embedded_array(&html_content, report_data::DASHBOARD_DATA, "variables")?;
+
+    // (live, dropped) counts per variable name, expected vs rendered
+    let mut expected: HashMap<&str, (usize, usize)> = HashMap::new();
+    for detail in variable_details.values() {
+        let counts = expected.entry(detail.name.as_str()).or_default();
+        match detail.lifecycle_stage {
+            LifecycleStage::Deallocated => counts.1 += 1,
+            _ => counts.0 += 1,
+        }
+    }
+    let mut rendered: HashMap<&str, (usize, usize)> = HashMap::new();
+    for variable in &rendered_variables {
+        let name = variable["name"].as_str().unwrap_or_default();
+        let counts = rendered.entry(name).or_default();
+        match variable["state"].as_str() {
+            Some("Active") => counts.0 += 1,
+            _ => counts.1 += 1,
+        }
+    }
+
+    let mut names: Vec<_> = expected.keys().copied().collect();
+    names.sort_unstable();
+
+    let mut mismatches = 0;
+    println!("♻️  variable lifecycle (live/dropped):");
+    for name in names {
+        let want = expected[name];
+        let got = rendered.get(name).copied().unwrap_or_default();
+        if want == got {
+            println!("✅ {}: {} live, {} dropped", name, want.0, want.1);
+        } else {
+            mismatches += 1;
+            println!("❌ {}: expected {} live/{} dropped, dashboard shows {} live/{} dropped", name, want.0, want.1, got.0, got.1);
+        }
+    }
+
+    let dropped: usize = expected.values().map(|c| c.1).sum();
+    if dropped == 0 {
+        println!("❌ No dropped variables were observed");
+        return Err("no dropped variables observed".into());
+    }
+    if mismatches == 0 {
+        println!("✅ Dashboard distinguishes {} dropped variables from live ones", dropped);
+        Ok(())
+    } else {
+        println!("❌ {} variables show the wrong lifecycle stage", mismatches);
+        Err(format!("{} lifecycle stage mismatch(es)", mismatches).into())
+    }
+}
+
+/// Deserialize the embedded variables and diff them against the input
+/// details field by field (size, thread, allocation count, state)
+fn verify_variable_round_trip(
+    variable_details: &HashMap<String, memscope_rs::export::fixed_hybrid_template::VariableDetail>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let html_content = std::fs::read_to_string("deep_inspector_real_data_verification.html")?;
+    let embedded = report_roundtrip::embedded_variables(&html_content)?;
+    let mismatches = report_roundtrip::diff_variables(variable_details.values(), &embedded);
+    report_roundtrip::report_mismatches("embedded variables", variable_details.len(), &mismatches)?;
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Render the same template from a dataset sharing no value with the real
+/// one; numbers, percentages or labels shown in both are template placeholders
+/// (e.g. a hardcoded "15%" memory attribution)
+fn verify_no_placeholder_values(
+    template: &FixedHybridTemplate,
+    hybrid_data: &memscope_rs::export::fixed_hybrid_template::HybridAnalysisData,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let html_content = std::fs::read_to_string("deep_inspector_real_data_verification.html")?;
+    let other_html = template.generate_hybrid_dashboard(&placeholder_values::disjoint_dataset(hybrid_data))?;
+    let shared = placeholder_values::shared_values(&html_content, &other_html);
+
+    if shared.is_empty() {
+        println!("✅ Every displayed number, percentage and label follows the data");
+        Ok(())
+    } else {
+        println!("❌ {} displayed values stay identical for a disjoint dataset:", shared.len());
+        for value in &shared {
+            println!("   • {}", value);
+        }
+        Err(format!("{} placeholder values in dashboard", shared.len()).into())
+    }
+}
+
+/// Check that every performance series has one point per sample and that the
+/// dashboard renders each of them with the same number of points
+fn verify_performance_charts(
+    metrics: &memscope_rs::export::fixed_hybrid_template::PerformanceTimeSeries,
+    sample_count: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let html_content = std::fs::read_to_string("deep_inspector_real_data_verification.html")?;
+
+    let as_f64 = |values: &[u64]| values.iter().map(|&v| v as f64).collect::<Vec<_>>();
+    let mut series: Vec<(String, Vec<f64>)> = vec![
+        ("cpu_usage".to_string(), metrics.cpu_usage.clone()),
+        ("memory_usage".to_string(), as_f64(&metrics.memory_usage)),
+        ("io_operations".to_string(), as_f64(&metrics.io_operations)),
+        ("network_bytes".to_string(), as_f64(&metrics.network_bytes)),
+        ("timestamps".to_string(), as_f64(&metrics.timestamps)),
+    ];
+    for (thread_id, values) in &metrics.thread_cpu_breakdown {
+        series.push((format!("thread_cpu_breakdown[{}]", thread_id), values.clone()));
+    }
+    for (thread_id, values) in &metrics.thread_memory_breakdown {
+        series.push((format!("thread_memory_breakdown[{}]", thread_id), as_f64(values)));
+    }
+
+    let mut failures = Vec::new();
+    if sample_count < 2 {
+        failures.push(format!("only {} resource samples taken", sample_count));
+    }
+    if metrics.thread_cpu_breakdown.is_empty() || metrics.thread_memory_breakdown.is_empty() {
+        failures.push("per-thread breakdowns are empty".to_string());
+    }
+
+    println!("📈 performance charts ({} samples):", sample_count);
+    for (name, values) in &series {
+        if report_data::is_trivial_series(values) {
+            println!("⚠️  {}: all {} points are zero, not distinguishable in the report", name, values.len());
+            continue;
+        }
+        let rendered = report_data::contains_series(&html_content, values);
+        if values.len() == sample_count && rendered {
+            println!("✅ {}: {} points", name, values.len());
+        } else {
+            println!("❌ {}: {} points, rendered with matching points: {}", name, values.len(), rendered);
+            failures.push(format!("{} does not chart {} points", name, sample_count));
+        }
+    }
+
+    if failures.is_empty() {
+        println!("✅ All {} performance series chart one point per sample", series.len());
+        Ok(())
+    } else {
+        println!("❌ {} performance chart problem(s)", failures.len());
+        Err(failures.join("; ").into())
+    }
+}
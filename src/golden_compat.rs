@@ -0,0 +1,422 @@
+//! Golden .memscope Compatibility Across memscope-rs Revisions
+//!
+//! `golden/` holds `.memscope` files, each next to a JSON manifest naming the
+//! memscope-rs revision that wrote it and the allocations it was written
+//! from. With the current dependency, every file must:
+//! 1. Decode with `BinaryReader::read_all` to the manifest's allocation count
+//! 2. Keep pointer, size, names, thread and timestamps of every allocation
+//! 3. Convert with `parse_binary_to_html_direct` into a report with
+//!    parseable JavaScript that embeds the same number of allocations
+//!
+//! `--record <scenario>` exports a `.toml` or `.json` scenario with the
+//! revision pinned in `Cargo.toml` and adds the file to the corpus, its
+//! manifest taken from the scenario's allocations. Record a file before
+//! moving the pin; the check then covers every revision recorded so far.
+
+use memscope_rs::core::types::AllocationInfo;
+use memscope_rs::export::binary;
+use memscope_stress_test::report_roundtrip::{self, FieldMismatch};
+use memscope_stress_test::{cli, js_syntax, scenario};
+use serde::{Deserialize, Serialize};
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Directory holding the corpus
+const CORPUS_DIR: &str = "./golden";
+
+/// Flag recording a scenario into the corpus instead of checking it
+const RECORD_FLAG: &str = "--record";
+
+/// Manifest pinning the memscope-rs revision new files are written by
+const CARGO_MANIFEST: &str = "./Cargo.toml";
+
+/// What a golden file was written from
+#[derive(Debug, Serialize, Deserialize)]
+struct GoldenManifest {
+    /// memscope-rs git revision that wrote the file
+    memscope_revision: String,
+    /// Scenario the allocations were built from
+    source: String,
+    allocation_count: usize,
+    allocations: Vec<GoldenAllocation>,
+}
+
+/// Fields of an allocation every revision has to keep decoding
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct GoldenAllocation {
+    ptr: usize,
+    size: usize,
+    var_name: Option<String>,
+    type_name: Option<String>,
+    scope_name: Option<String>,
+    thread_id: String,
+    timestamp_alloc: u64,
+    timestamp_dealloc: Option<u64>,
+}
+
+impl From<&AllocationInfo> for GoldenAllocation {
+    fn from(allocation: &AllocationInfo) -> Self {
+        GoldenAllocation {
+            ptr: allocation.ptr,
+            size: allocation.size,
+            var_name: allocation.var_name.clone(),
+            type_name: allocation.type_name.clone(),
+            scope_name: allocation.scope_name.clone(),
+            thread_id: allocation.thread_id.clone(),
+            timestamp_alloc: allocation.timestamp_alloc,
+            timestamp_dealloc: allocation.timestamp_dealloc,
+        }
+    }
+}
+
+/// Outcome for one golden file
+struct FileResult {
+    name: String,
+    revision: String,
+    expected: usize,
+    decoded: Option<usize>,
+    mismatches: usize,
+    html_allocations: Option<usize>,
+    problems: Vec<String>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🏛️  Golden .memscope Compatibility Across memscope-rs Revisions");
+    println!("===============================================================");
+    if let Some(file) = cli::flag_value(RECORD_FLAG) {
+        return record_into_corpus(Path::new(&file));
+    }
+
+    let output_dir = PathBuf::from("./Memoryanalysis/golden_compat");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+
+    let corpus = corpus_files(Path::new(CORPUS_DIR))?;
+    if corpus.is_empty() {
+        println!("   ❌ FAILED: no .memscope files in {}", CORPUS_DIR);
+        println!(
+            "   💡 Record one with `cargo run --bin golden_compat -- {} scenarios/comprehensive_demo.toml`",
+            RECORD_FLAG
+        );
+        return Err(format!("empty golden corpus at {}", CORPUS_DIR).into());
+    }
+    println!("   🗃️  {} golden file(s) in {}", corpus.len(), CORPUS_DIR);
+
+    let mut results = Vec::new();
+    for binary_path in &corpus {
+        results.push(check_file(binary_path, &output_dir)?);
+    }
+
+    let report_path = output_dir.join("golden_compat.md");
+    std::fs::write(&report_path, render_report(&results))?;
+    println!("\n📄 Compatibility report: {}", report_path.display());
+
+    let broken: Vec<&FileResult> = results.iter().filter(|r| !r.problems.is_empty()).collect();
+    if broken.is_empty() {
+        println!(
+            "   ✅ SUCCESS: all {} golden file(s) decode with the current memscope-rs",
+            results.len()
+        );
+        Ok(())
+    } else {
+        println!(
+            "   ❌ FAILED: {} of {} golden file(s) no longer decode as recorded",
+            broken.len(),
+            results.len()
+        );
+        Err(format!("{}: {}", broken[0].name, broken[0].problems[0]).into())
+    }
+}
+
+/// Every `.memscope` file in `dir`, sorted by name
+fn corpus_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "memscope"))
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Decode `binary_path`, compare it with its manifest and convert it to HTML
+fn check_file(
+    binary_path: &Path,
+    output_dir: &Path,
+) -> Result<FileResult, Box<dyn std::error::Error>> {
+    let name = binary_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let manifest_path = binary_path.with_extension("json");
+    let manifest: GoldenManifest = serde_json::from_str(
+        &std::fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("Missing manifest {}: {}", manifest_path.display(), e))?,
+    )
+    .map_err(|e| format!("Invalid manifest {}: {}", manifest_path.display(), e))?;
+    println!(
+        "\n🔍 {} (memscope-rs {}, {} allocations)",
+        name,
+        short_revision(&manifest.memscope_revision),
+        manifest.allocation_count
+    );
+
+    let mut result = FileResult {
+        name: name.clone(),
+        revision: manifest.memscope_revision.clone(),
+        expected: manifest.allocation_count,
+        decoded: None,
+        mismatches: 0,
+        html_allocations: None,
+        problems: Vec::new(),
+    };
+
+    match binary::BinaryReader::new(binary_path).and_then(|mut reader| reader.read_all()) {
+        Ok(decoded) => {
+            result.decoded = Some(decoded.len());
+            if decoded.len() == manifest.allocation_count {
+                println!("   ✅ Decoded {} allocations", decoded.len());
+            } else {
+                println!(
+                    "   ❌ Decoded {} allocations, manifest records {}",
+                    decoded.len(),
+                    manifest.allocation_count
+                );
+                result.problems.push(format!(
+                    "decoded {} allocations instead of {}",
+                    decoded.len(),
+                    manifest.allocation_count
+                ));
+            }
+            let mismatches = diff_key_fields(&manifest.allocations, &decoded);
+            result.mismatches = mismatches.len();
+            if let Err(problem) = report_roundtrip::report_mismatches(
+                "golden allocations",
+                decoded.len(),
+                &mismatches,
+            ) {
+                result.problems.push(problem);
+            }
+        }
+        Err(e) => {
+            println!("   ❌ BinaryReader failed: {}", e);
+            result.problems.push(format!("does not decode: {}", e));
+        }
+    }
+
+    let html_path = output_dir.join(format!("{}.html", name));
+    let title = format!("Golden {}", name);
+    match binary::parse_binary_to_html_direct(binary_path, &html_path, &title) {
+        Ok(_) => {
+            let embedded = std::fs::read_to_string(&html_path)
+                .map_err(|e| format!("HTML report unreadable: {}", e))
+                .and_then(|html| report_roundtrip::embedded_allocations(&html));
+            match embedded {
+                Ok(embedded) if embedded.len() == manifest.allocation_count => {
+                    println!("   ✅ HTML report embeds {} allocations", embedded.len());
+                    result.html_allocations = Some(embedded.len());
+                }
+                Ok(embedded) => {
+                    println!(
+                        "   ❌ HTML report embeds {} allocations, manifest records {}",
+                        embedded.len(),
+                        manifest.allocation_count
+                    );
+                    result.html_allocations = Some(embedded.len());
+                    result.problems.push(format!(
+                        "HTML embeds {} allocations instead of {}",
+                        embedded.len(),
+                        manifest.allocation_count
+                    ));
+                }
+                Err(e) => {
+                    println!("   ❌ {}", e);
+                    result.problems.push(e);
+                }
+            }
+            if let Err(e) = js_syntax::verify_report(&html_path) {
+                result.problems.push(e);
+            }
+        }
+        Err(e) => {
+            println!("   ❌ parse_binary_to_html_direct failed: {}", e);
+            result
+                .problems
+                .push(format!("HTML conversion failed: {}", e));
+        }
+    }
+    Ok(result)
+}
+
+/// Mismatches in the fields a golden file pins, compared by position
+fn diff_key_fields(
+    expected: &[GoldenAllocation],
+    decoded: &[AllocationInfo],
+) -> Vec<FieldMismatch> {
+    let mut mismatches = Vec::new();
+    for (expected, decoded) in expected.iter().zip(decoded) {
+        let found = GoldenAllocation::from(decoded);
+        if *expected == found {
+            continue;
+        }
+        let record = format!(
+            "{:#x} ({})",
+            expected.ptr,
+            expected.var_name.as_deref().unwrap_or("unnamed")
+        );
+        let mut field = |field: &'static str, expected: String, found: String| {
+            if expected != found {
+                mismatches.push(FieldMismatch {
+                    record: record.clone(),
+                    field,
+                    expected,
+                    found,
+                });
+            }
+        };
+        field(
+            "ptr",
+            format!("{:#x}", expected.ptr),
+            format!("{:#x}", found.ptr),
+        );
+        field("size", expected.size.to_string(), found.size.to_string());
+        field(
+            "var_name",
+            format!("{:?}", expected.var_name),
+            format!("{:?}", found.var_name),
+        );
+        field(
+            "type_name",
+            format!("{:?}", expected.type_name),
+            format!("{:?}", found.type_name),
+        );
+        field(
+            "scope_name",
+            format!("{:?}", expected.scope_name),
+            format!("{:?}", found.scope_name),
+        );
+        field(
+            "thread_id",
+            format!("{:?}", expected.thread_id),
+            format!("{:?}", found.thread_id),
+        );
+        field(
+            "timestamp_alloc",
+            expected.timestamp_alloc.to_string(),
+            found.timestamp_alloc.to_string(),
+        );
+        field(
+            "timestamp_dealloc",
+            format!("{:?}", expected.timestamp_dealloc),
+            format!("{:?}", found.timestamp_dealloc),
+        );
+    }
+    mismatches
+}
+
+/// Export the scenario at `path` with the pinned revision and add it to the
+/// corpus, with a manifest of the allocations it was written from
+fn record_into_corpus(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let cargo_manifest = std::fs::read_to_string(CARGO_MANIFEST)?;
+    let revision = pinned_memscope_revision(&cargo_manifest)
+        .ok_or_else(|| format!("memscope-rs has no `rev` pin in {}", CARGO_MANIFEST))?;
+    let allocations = scenario::load(path)?.allocations()?;
+    let stem = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .ok_or_else(|| format!("{} has no file name", path.display()))?;
+
+    let name = format!("{}-{}", short_revision(&revision), stem);
+    let binary_path = Path::new(CORPUS_DIR).join(format!("{}.memscope", name));
+    if binary_path.exists() {
+        return Err(format!("{} is already in the corpus", binary_path.display()).into());
+    }
+    std::fs::create_dir_all(CORPUS_DIR)?;
+    binary::export_to_binary(&allocations, &binary_path)?;
+
+    let manifest = GoldenManifest {
+        memscope_revision: revision,
+        source: path.display().to_string(),
+        allocation_count: allocations.len(),
+        allocations: allocations.iter().map(GoldenAllocation::from).collect(),
+    };
+    std::fs::write(
+        binary_path.with_extension("json"),
+        serde_json::to_string_pretty(&manifest)? + "\n",
+    )?;
+    println!(
+        "   ✅ Recorded {} with {} allocations",
+        binary_path.display(),
+        manifest.allocation_count
+    );
+    Ok(())
+}
+
+/// Revision in the `rev = "..."` pin of the memscope-rs dependency
+fn pinned_memscope_revision(cargo_manifest: &str) -> Option<String> {
+    let dependency = cargo_manifest
+        .lines()
+        .find(|line| line.trim_start().starts_with("memscope-rs"))?;
+    dependency.match_indices("rev").find_map(|(idx, key)| {
+        let value = dependency[idx + key.len()..]
+            .trim_start()
+            .strip_prefix('=')?
+            .trim_start()
+            .strip_prefix('"')?;
+        value
+            .split_once('"')
+            .map(|(revision, _)| revision.to_string())
+    })
+}
+
+fn short_revision(revision: &str) -> &str {
+    revision.get(..7).unwrap_or(revision)
+}
+
+fn render_report(results: &[FileResult]) -> String {
+    let count = |value: Option<usize>| value.map_or_else(|| "—".to_string(), |v| v.to_string());
+
+    let mut report = String::new();
+    let _ = writeln!(report, "# Golden .memscope Compatibility\n");
+    let _ = writeln!(report, "- Corpus: `{}`", CORPUS_DIR);
+    let _ = writeln!(
+        report,
+        "- Files still decoding as recorded: {} of {}\n",
+        results.iter().filter(|r| r.problems.is_empty()).count(),
+        results.len()
+    );
+    let _ = writeln!(
+        report,
+        "| File | Written by | Expected | Decoded | Field mismatches | In HTML | Status |"
+    );
+    let _ = writeln!(report, "|---|---|---:|---:|---:|---:|---|");
+    for result in results {
+        let _ = writeln!(
+            report,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            result.name,
+            short_revision(&result.revision),
+            result.expected,
+            count(result.decoded),
+            result.mismatches,
+            count(result.html_allocations),
+            if result.problems.is_empty() {
+                "✅"
+            } else {
+                "❌"
+            }
+        );
+    }
+    for result in results.iter().filter(|r| !r.problems.is_empty()) {
+        let _ = writeln!(report, "\n## {}\n", result.name);
+        for problem in &result.problems {
+            let _ = writeln!(report, "- {}", problem);
+        }
+    }
+    report
+}
@@ -0,0 +1,247 @@
+//! HTML Escaping of Hostile Identifiers in Generated Reports
+//!
+//! This scenario feeds variable names and type strings containing
+//! `</script>`, quotes, `<img onerror>`, template syntax, emoji, RTL text and
+//! line separators into:
+//! 1. `VariableDetail` name/type rendered by `generate_hybrid_dashboard` in
+//!    every `RenderMode`
+//! 2. `AllocationInfo` `var_name`/`type_name`/`scope_name` exported to binary
+//!    and rendered by `parse_binary_to_html_direct`
+//!
+//! `export_comprehensive_analysis` takes no user identifiers, so it has no
+//! input to poison. Every report must keep its embedded JavaScript parseable,
+//! contain none of the payload markup unescaped, and carry each identifier
+//! through its embedded data unchanged.
+
+use memscope_rs::export::binary;
+use memscope_rs::export::fixed_hybrid_template::{
+    FixedHybridTemplate, LifecycleStage, VariableDetail,
+};
+use memscope_stress_test::allocation::allocation;
+use memscope_stress_test::hybrid_data::{render_modes, synthetic_hybrid_data, HybridDataShape};
+use memscope_stress_test::{js_syntax, report_data, seed};
+use rand::Rng;
+
+use std::path::{Path, PathBuf};
+
+/// Identifiers that break naive string interpolation into HTML or JavaScript
+const HOSTILE_IDENTIFIERS: [&str; 9] = [
+    "buf</script><script>alert('msx_script')</script>",
+    "<img src=x onerror=alert('msx_img')>",
+    r#"quoted_"double"_'single'_`tick`"#,
+    r"back\slash\\n_not_a_newline",
+    "tpl_${window.msx_tpl}_{{msx_mustache}}",
+    "&amp;_&lt;entity&gt;_&#39;",
+    "emoji_🦀🔥_buffer",
+    "rtl_\u{202E}txt.exe_مخزن_שלום",
+    "line\nbreak\u{2028}separator\u{2029}end",
+];
+
+/// Markup that must never appear verbatim in a report
+const RAW_PAYLOADS: [&str; 3] = [
+    "<script>alert('msx_script')",
+    "<img src=x onerror=alert('msx_img')>",
+    "</script><script>",
+];
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🛡️  HTML Escaping of Hostile Identifiers in Generated Reports");
+    println!("=============================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    let mut rng = seed::rng(seed);
+
+    let output_dir = PathBuf::from("./Memoryanalysis/hostile_identifiers");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let mut failures = Vec::new();
+
+    // Hybrid dashboard: hostile names and types among ordinary variables
+    let shape = HybridDataShape {
+        variables: 24,
+        threads: 4,
+        variables_per_task: 4,
+        samples: 10,
+    };
+    let mut data = synthetic_hybrid_data(shape, &mut rng);
+    let hostile_task = shape.variables / shape.variables_per_task + 1;
+    for (idx, identifier) in HOSTILE_IDENTIFIERS.iter().enumerate() {
+        data.variable_registry.insert(
+            format!("hostile_{}", idx),
+            VariableDetail {
+                name: identifier.to_string(),
+                type_info: hostile_type(identifier),
+                thread_id: 1,
+                task_id: Some(hostile_task),
+                allocation_count: 1,
+                memory_usage: rng.random_range(64..=65536),
+                lifecycle_stage: LifecycleStage::Active,
+            },
+        );
+    }
+    data.thread_task_mapping
+        .entry(1)
+        .or_default()
+        .push(hostile_task);
+
+    println!(
+        "\n🧪 Hybrid dashboard ({} hostile variables):",
+        HOSTILE_IDENTIFIERS.len()
+    );
+    for (label, render_mode) in render_modes() {
+        let html = FixedHybridTemplate::new(4, shape.variables / shape.variables_per_task + 1)
+            .with_render_mode(render_mode())
+            .with_variable_details(true)
+            .with_enhanced_insights(true)
+            .generate_hybrid_dashboard(&data)?;
+        let html_path = output_dir.join(format!("hybrid_{}.html", label));
+        std::fs::write(&html_path, format!("{}{}", html, seed::html_comment(seed)))?;
+
+        let rendered_names =
+            report_data::embedded_array(&html, report_data::DASHBOARD_DATA, "variables").map(
+                |variables| {
+                    variables
+                        .iter()
+                        .filter_map(|v| v["name"].as_str().map(str::to_string))
+                        .collect()
+                },
+            );
+        let expected: Vec<String> = HOSTILE_IDENTIFIERS.iter().map(|s| s.to_string()).collect();
+        failures.extend(check_report(&html_path, &html, &expected, rendered_names));
+    }
+
+    // Binary dashboard: hostile var_name, type_name and scope_name
+    let allocations: Vec<_> = HOSTILE_IDENTIFIERS
+        .iter()
+        .enumerate()
+        .map(|(idx, identifier)| {
+            allocation(
+                0x7000 + idx * 0x100,
+                rng.random_range(64..=65536),
+                identifier,
+                &hostile_type(identifier),
+                &hostile_scope(identifier),
+                "main",
+            )
+        })
+        .collect();
+    let binary_path = output_dir.join("hostile_identifiers.memscope");
+    let html_path = output_dir.join("binary_dashboard.html");
+    binary::export_to_binary(&allocations, &binary_path)?;
+    binary::parse_binary_to_html_direct(&binary_path, &html_path, "Hostile Identifiers")?;
+    let html = std::fs::read_to_string(&html_path)?;
+
+    println!(
+        "\n🧪 Binary dashboard ({} hostile allocations):",
+        allocations.len()
+    );
+    let rendered_strings =
+        report_data::embedded_object(&html, report_data::ANALYSIS_DATA).map(|data| {
+            data["allocations"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .flat_map(|a| {
+                    ["var_name", "type_name", "scope_name"]
+                        .into_iter()
+                        .filter_map(|field| a[field].as_str().map(str::to_string))
+                })
+                .collect()
+        });
+    let expected: Vec<String> = HOSTILE_IDENTIFIERS
+        .iter()
+        .flat_map(|s| [s.to_string(), hostile_type(s), hostile_scope(s)])
+        .collect();
+    failures.extend(check_report(&html_path, &html, &expected, rendered_strings));
+
+    seed::announce(seed);
+    if failures.is_empty() {
+        println!(
+            "   ✅ SUCCESS: every report escapes hostile identifiers and keeps valid JavaScript"
+        );
+        Ok(())
+    } else {
+        println!("   ❌ FAILED: {} escaping problem(s)", failures.len());
+        Err(failures.join("; ").into())
+    }
+}
+
+fn hostile_type(identifier: &str) -> String {
+    format!("HashMap<String, {}>", identifier)
+}
+
+fn hostile_scope(identifier: &str) -> String {
+    format!("scope::{}", identifier)
+}
+
+/// Check one report: parseable scripts, no raw payload markup, and every
+/// expected identifier present unchanged in the embedded data
+fn check_report(
+    path: &Path,
+    html: &str,
+    expected: &[String],
+    rendered: Result<Vec<String>, String>,
+) -> Vec<String> {
+    let mut failures = Vec::new();
+    let report = path.display();
+
+    let syntax_errors = js_syntax::check_html(html);
+    if syntax_errors.is_empty() {
+        println!("   ✅ {}: embedded JavaScript parses", report);
+    } else {
+        println!(
+            "   ❌ {}: {} JavaScript syntax error(s)",
+            report,
+            syntax_errors.len()
+        );
+        for error in syntax_errors.iter().take(5) {
+            println!("      • {}", error);
+        }
+        failures.push(format!("{}: {}", report, syntax_errors[0]));
+    }
+
+    for payload in RAW_PAYLOADS {
+        if html.contains(payload) {
+            println!("   ❌ {}: unescaped payload {:?}", report, payload);
+            failures.push(format!("{}: unescaped {:?}", report, payload));
+        }
+    }
+
+    match rendered {
+        Ok(rendered) => {
+            let missing: Vec<_> = expected
+                .iter()
+                .filter(|identifier| !rendered.contains(identifier))
+                .collect();
+            if missing.is_empty() {
+                println!(
+                    "   ✅ {}: {} identifiers preserved in embedded data",
+                    report,
+                    expected.len()
+                );
+            } else {
+                for identifier in &missing {
+                    println!(
+                        "   ❌ {}: identifier not preserved: {:?}",
+                        report, identifier
+                    );
+                }
+                failures.push(format!(
+                    "{}: {} identifiers altered or missing",
+                    report,
+                    missing.len()
+                ));
+            }
+        }
+        Err(e) => {
+            println!("   ❌ {}: {}", report, e);
+            failures.push(format!("{}: {}", report, e));
+        }
+    }
+
+    failures
+}
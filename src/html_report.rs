@@ -0,0 +1,123 @@
+//! DOM-based inspection of generated HTML reports
+//!
+//! Reports are parsed with an HTML5 parser (`scraper`/`html5ever`), so checks
+//! look at real elements (titles, headings, ids, table rows) instead of
+//! counting substrings that may only occur inside scripts or styles.
+
+use scraper::{ElementRef, Html, Selector};
+
+/// Sections the binary dashboard must contain, by element id
+pub const BINARY_DASHBOARD_SECTION_IDS: [&str; 10] = [
+    "total-allocations",
+    "total-memory",
+    "active-variables",
+    "peak-memory",
+    "allocTable",
+    "unsafeTable",
+    "memoryDistributionViz",
+    "lifecycleVisualizationContainer",
+    "graph",
+    "theme-toggle",
+];
+
+/// Rows of one `<table>` in the static document
+#[derive(Debug, Clone)]
+pub struct TableSummary {
+    /// Id of the table, or of its `<tbody>` when only that carries one
+    pub id: Option<String>,
+    pub header_rows: usize,
+    /// Rows in `<tbody>`; tables filled by scripts have none until run
+    pub body_rows: usize,
+}
+
+/// A parsed HTML report
+pub struct HtmlReport {
+    document: Html,
+}
+
+impl HtmlReport {
+    pub fn parse(html: &str) -> Self {
+        Self {
+            document: Html::parse_document(html),
+        }
+    }
+
+    /// Errors the HTML5 parser recovered from
+    pub fn parse_errors(&self) -> Vec<String> {
+        self.document.errors.iter().map(|e| e.to_string()).collect()
+    }
+
+    /// Text of the `<title>` element
+    pub fn title(&self) -> Option<String> {
+        self.select("title").ok()?.first().map(element_text)
+    }
+
+    /// Text of every heading with `tag` (e.g. `h1`), in document order
+    pub fn headings(&self, tag: &str) -> Vec<String> {
+        self.select(tag)
+            .map(|headings| headings.iter().map(element_text).collect())
+            .unwrap_or_default()
+    }
+
+    /// Which of `ids` have no element in the document
+    pub fn missing_ids<'a>(&self, ids: &[&'a str]) -> Vec<&'a str> {
+        ids.iter()
+            .copied()
+            .filter(|id| {
+                self.select(&format!("[id=\"{}\"]", id))
+                    .map_or(true, |found| found.is_empty())
+            })
+            .collect()
+    }
+
+    /// Elements matching a CSS `selector`
+    pub fn select(&self, selector: &str) -> Result<Vec<ElementRef<'_>>, String> {
+        let parsed = Selector::parse(selector)
+            .map_err(|e| format!("Invalid selector '{}': {}", selector, e))?;
+        Ok(self.document.select(&parsed).collect())
+    }
+
+    /// Header and body row counts of every table
+    pub fn tables(&self) -> Vec<TableSummary> {
+        let rows = |table: &ElementRef, selector: &str| {
+            Selector::parse(selector).map_or(0, |s| table.select(&s).count())
+        };
+
+        self.select("table")
+            .unwrap_or_default()
+            .iter()
+            .map(|table| {
+                let tbody_id = Selector::parse("tbody[id]").ok().and_then(|s| {
+                    table
+                        .select(&s)
+                        .next()
+                        .and_then(|tbody| tbody.value().id().map(str::to_string))
+                });
+                TableSummary {
+                    id: table.value().id().map(str::to_string).or(tbody_id),
+                    header_rows: rows(table, "thead tr"),
+                    body_rows: rows(table, "tbody tr"),
+                }
+            })
+            .collect()
+    }
+
+    /// Concatenated source of every `<script>` element
+    pub fn script_source(&self) -> String {
+        self.select("script")
+            .unwrap_or_default()
+            .iter()
+            .map(|script| script.text().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Text content of an element with whitespace collapsed
+pub fn element_text(element: &ElementRef) -> String {
+    element
+        .text()
+        .flat_map(str::split_whitespace)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,144 @@
+//! Seeded synthetic `HybridAnalysisData` for hybrid dashboard scenarios
+//!
+//! Variables are spread round-robin over threads and grouped into tasks of
+//! consecutive variables, so the thread → task mapping is known exactly.
+
+use memscope_rs::export::fixed_hybrid_template::{
+    HybridAnalysisData, LifecycleStage, PerformanceTimeSeries, RenderMode, VariableDetail,
+};
+use memscope_rs::lockfree::analysis::LockfreeAnalysis;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Shape of a synthetic dataset
+#[derive(Debug, Clone, Copy)]
+pub struct HybridDataShape {
+    pub variables: usize,
+    pub threads: usize,
+    pub variables_per_task: usize,
+    /// Points in every performance series
+    pub samples: usize,
+}
+
+const VARIABLE_KINDS: [(&str, &str, u64); 6] = [
+    ("request_buffer", "Vec<u8>", 4096),
+    ("user_cache", "HashMap<String, User>", 65536),
+    ("shared_state", "Arc<Mutex<Vec<u64>>>", 1024),
+    ("frame_data", "Vec<u32>", 262144),
+    ("task_handle", "Box<dyn Future<Output = ()>>", 256),
+    ("scratch_string", "String", 128),
+];
+
+/// Builds a fresh `RenderMode`, one per template
+pub type RenderModeFn = fn() -> RenderMode;
+
+/// Every `RenderMode` with a label for output file names
+pub fn render_modes() -> [(&'static str, RenderModeFn); 3] {
+    let modes: [RenderModeFn; 3] = [
+        || RenderMode::Comprehensive,
+        || RenderMode::ThreadFocused,
+        || RenderMode::VariableDetailed,
+    ];
+    modes.map(|mode| (render_mode_label(&mode()), mode))
+}
+
+/// File-name label of `mode`
+///
+/// The match is exhaustive, so a new `RenderMode` variant stops the build here
+/// until it is labelled and added to [`render_modes`].
+pub fn render_mode_label(mode: &RenderMode) -> &'static str {
+    match mode {
+        RenderMode::Comprehensive => "comprehensive",
+        RenderMode::ThreadFocused => "thread_focused",
+        RenderMode::VariableDetailed => "variable_detailed",
+    }
+}
+
+/// Build a dataset of `shape` with seeded sizes, stages and series
+pub fn synthetic_hybrid_data(shape: HybridDataShape, rng: &mut ChaCha8Rng) -> HybridAnalysisData {
+    let threads = shape.threads.max(1);
+    let variables_per_task = shape.variables_per_task.max(1);
+
+    let mut variable_registry = HashMap::with_capacity(shape.variables);
+    let mut thread_tasks: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+    let mut total_memory = 0u64;
+
+    for idx in 0..shape.variables {
+        let (kind, type_info, nominal_size) = VARIABLE_KINDS[idx % VARIABLE_KINDS.len()];
+        let thread_id = idx % threads + 1;
+        let task_id = idx / variables_per_task + 1;
+        let memory_usage = rng.random_range(nominal_size / 2..=nominal_size * 2);
+        let lifecycle_stage = match rng.random_range(0..4) {
+            0 => LifecycleStage::Allocated,
+            1 => LifecycleStage::Active,
+            2 => LifecycleStage::Shared,
+            _ => LifecycleStage::Deallocated,
+        };
+
+        let name = format!("{}_{}", kind, idx);
+        total_memory += memory_usage;
+        thread_tasks.entry(thread_id).or_default().insert(task_id);
+        variable_registry.insert(
+            format!("{}_{:x}", name, 0x1000 + idx * 0x40),
+            VariableDetail {
+                name,
+                type_info: type_info.to_string(),
+                thread_id,
+                task_id: Some(task_id),
+                allocation_count: rng.random_range(1..=8),
+                memory_usage,
+                lifecycle_stage,
+            },
+        );
+    }
+
+    let mut lockfree_analysis = LockfreeAnalysis::new();
+    lockfree_analysis.summary.total_allocations = shape.variables as u64;
+    lockfree_analysis.summary.peak_memory_usage = total_memory as usize;
+
+    HybridAnalysisData {
+        variable_registry,
+        lockfree_analysis: Some(lockfree_analysis),
+        thread_task_mapping: thread_tasks
+            .into_iter()
+            .map(|(thread_id, tasks)| (thread_id, tasks.into_iter().collect()))
+            .collect(),
+        visualization_config: Default::default(),
+        performance_metrics: synthetic_time_series(shape.samples, threads, rng),
+    }
+}
+
+fn synthetic_time_series(
+    samples: usize,
+    threads: usize,
+    rng: &mut ChaCha8Rng,
+) -> PerformanceTimeSeries {
+    let thread_cpu_breakdown = (1..=threads)
+        .map(|tid| {
+            (
+                tid,
+                (0..samples).map(|_| rng.random_range(0.0..100.0)).collect(),
+            )
+        })
+        .collect();
+    let thread_memory_breakdown = (1..=threads)
+        .map(|tid| {
+            (
+                tid,
+                (0..samples).map(|_| rng.random_range(0..1 << 20)).collect(),
+            )
+        })
+        .collect();
+
+    PerformanceTimeSeries {
+        cpu_usage: (0..samples).map(|_| rng.random_range(0.0..100.0)).collect(),
+        memory_usage: (0..samples).map(|_| rng.random_range(0..1 << 30)).collect(),
+        io_operations: (0..samples).map(|_| rng.random_range(0..10_000)).collect(),
+        network_bytes: (0..samples).map(|_| rng.random_range(0..1 << 24)).collect(),
+        timestamps: (0..samples as u64).map(|i| i * 100).collect(),
+        thread_cpu_breakdown,
+        thread_memory_breakdown,
+    }
+}
@@ -13,6 +13,8 @@ use memscope_rs::lockfree::tracker::{
 use memscope_rs::lockfree::{
     export_comprehensive_analysis, IntegratedProfilingSession, PlatformResourceCollector,
 };
+use memscope_stress_test::{js_syntax, seed};
+use rand::Rng;
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -24,7 +26,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🔍 Verified Selective Tracking with Platform Resource Monitoring");
     println!("================================================================");
     println!("   50 threads total, verifying ONLY EVEN threads tracked");
-    println!("   + Real-time CPU, GPU, IO monitoring across all threads\n");
+    println!("   + Real-time CPU, GPU, IO monitoring across all threads");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    println!();
 
     let demo_start = Instant::now();
     let output_dir = std::path::PathBuf::from("./Memoryanalysis");
@@ -34,6 +39,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         std::fs::remove_dir_all(&output_dir)?;
     }
     std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
 
     let total_operations = Arc::new(AtomicUsize::new(0));
     let tracking_log = Arc::new(Mutex::new(Vec::new()));
@@ -111,6 +117,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             thread::spawn(move || -> Result<(), String> {
                 run_enhanced_verified_worker(
                     thread_idx,
+                    seed,
                     &output_dir,
                     &total_operations,
                     &tracking_log,
@@ -262,6 +269,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         untracked_threads
     );
     println!("   ⏱️  Duration: {:?}", simulation_duration);
+    seed::announce(seed);
 
     // Verify tracking was selective
     verify_selective_tracking_logic(&tracked_threads, &untracked_threads)?;
@@ -300,6 +308,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 html_file.display(),
                 file_size
             );
+            js_syntax::verify_report(&html_file)?;
         } else {
             println!("   ❌ HTML dashboard not found");
         }
@@ -323,9 +332,13 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Allocation sizes a worker can be assigned: 1KB, 4KB, 16KB, 8KB
+const ALLOC_SIZE_CLASSES: [usize; 4] = [1024, 4096, 16384, 8192];
+
 /// Enhanced worker function with more intensive workload for resource monitoring
 fn run_enhanced_verified_worker(
     thread_idx: usize,
+    seed: u64,
     output_dir: &std::path::Path,
     total_operations: &Arc<AtomicUsize>,
     tracking_log: &Arc<Mutex<Vec<(usize, bool)>>>,
@@ -335,6 +348,7 @@ fn run_enhanced_verified_worker(
     // Enhanced workload for better resource monitoring
     let start_time = Instant::now();
     let mut local_ops = 0;
+    let mut rng = seed::stream_rng(seed, thread_idx);
 
     // Only EVEN threads should initialize tracking
     let should_track = thread_idx.is_multiple_of(2);
@@ -353,7 +367,10 @@ fn run_enhanced_verified_worker(
     }
 
     // Enhanced workload: More intensive operations for better resource monitoring
-    let iterations = 1000 + (thread_idx * 10); // Variable workload per thread
+    let iterations = rng.random_range(1000..1500); // Variable workload per thread
+
+    // Memory operations - each thread is assigned one allocation size class
+    let alloc_size = ALLOC_SIZE_CLASSES[rng.random_range(0..ALLOC_SIZE_CLASSES.len())];
 
     for i in 0..iterations {
         // CPU intensive work
@@ -362,14 +379,6 @@ fn run_enhanced_verified_worker(
             computation_result = computation_result.wrapping_mul(thread_idx as u64 + j as u64 + 1);
         }
 
-        // Memory operations - different patterns per thread
-        let alloc_size = match thread_idx % 4 {
-            0 => 1024,  // 1KB
-            1 => 4096,  // 4KB
-            2 => 16384, // 16KB
-            _ => 8192,  // 8KB
-        };
-
         let data = vec![0u8; alloc_size];
         let ptr = data.as_ptr() as usize;
 
@@ -0,0 +1,255 @@
+//! Hybrid Dashboard Scalability with 100k+ Tracked Variables
+//!
+//! This scenario renders seeded `HybridAnalysisData` with tens to hundreds of
+//! thousands of `VariableDetail` entries spread over many threads, and for
+//! every size records:
+//! 1. `generate_hybrid_dashboard` time
+//! 2. Peak resident memory growth while rendering
+//! 3. Size of the generated HTML
+//!
+//! The run fails when any of the three grows superlinearly with the variable
+//! count, or when the largest dashboard exceeds the HTML budget
+//! (`--html-budget-mb`, default 256).
+
+use memscope_rs::export::fixed_hybrid_template::{FixedHybridTemplate, RenderMode};
+use memscope_stress_test::hybrid_data::{synthetic_hybrid_data, HybridDataShape};
+use memscope_stress_test::{cli, process_memory, seed};
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Variable counts rendered, smallest first
+const VARIABLE_COUNTS: [usize; 5] = [10_000, 25_000, 50_000, 100_000, 200_000];
+
+/// Threads the variables are spread over
+const THREADS: usize = 64;
+
+/// Consecutive variables grouped into one task
+const VARIABLES_PER_TASK: usize = 16;
+
+/// Points in every performance series
+const SAMPLES: usize = 60;
+
+/// Fitted log-log growth exponent above which growth counts as superlinear
+const SUPERLINEAR_EXPONENT: f64 = 1.2;
+
+/// Flag overriding the HTML size budget for the largest dashboard
+const HTML_BUDGET_FLAG: &str = "--html-budget-mb";
+
+/// Default HTML size budget in MB
+const DEFAULT_HTML_BUDGET_MB: u64 = 256;
+
+/// Measurements for one variable count
+struct ScalePoint {
+    variables: usize,
+    generation_time: Duration,
+    /// Peak RSS growth over the pre-render RSS, if readable
+    peak_memory_bytes: Option<u64>,
+    html_bytes: usize,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("📈 Hybrid Dashboard Scalability with 100k+ Tracked Variables");
+    println!("============================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let html_budget_mb = match cli::flag_value(HTML_BUDGET_FLAG) {
+        Some(value) => value
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid {} value '{}': {}", HTML_BUDGET_FLAG, value, e))?,
+        None => DEFAULT_HTML_BUDGET_MB,
+    };
+    println!("   💾 HTML budget: {} MB", html_budget_mb);
+
+    let output_dir = PathBuf::from("./Memoryanalysis/hybrid_scalability");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let mut points = Vec::new();
+    for (idx, &variables) in VARIABLE_COUNTS.iter().enumerate() {
+        let shape = HybridDataShape {
+            variables,
+            threads: THREADS,
+            variables_per_task: VARIABLES_PER_TASK,
+            samples: SAMPLES,
+        };
+        let data = synthetic_hybrid_data(shape, &mut seed::stream_rng(seed, idx));
+        let template = FixedHybridTemplate::new(THREADS, variables / VARIABLES_PER_TASK)
+            .with_render_mode(RenderMode::Comprehensive)
+            .with_variable_details(true)
+            .with_enhanced_insights(true);
+
+        let baseline_rss = process_memory::current_rss_bytes();
+        let peak_reset = process_memory::reset_peak_rss();
+        let started = Instant::now();
+        let html = template.generate_hybrid_dashboard(&data)?;
+        let generation_time = started.elapsed();
+        let peak_memory_bytes = match (peak_reset, baseline_rss, process_memory::peak_rss_bytes()) {
+            (true, Some(baseline), Some(peak)) => Some(peak.saturating_sub(baseline)),
+            _ => None,
+        };
+
+        let point = ScalePoint {
+            variables,
+            generation_time,
+            peak_memory_bytes,
+            html_bytes: html.len(),
+        };
+        println!(
+            "   📦 {:>7} variables: {:>9.2} ms, peak +{}, HTML {:.2} MB",
+            point.variables,
+            point.generation_time.as_secs_f64() * 1000.0,
+            format_megabytes(point.peak_memory_bytes),
+            point.html_bytes as f64 / (1024.0 * 1024.0)
+        );
+        points.push(point);
+    }
+
+    let mut failures = Vec::new();
+
+    let time_exponent = growth_exponent(&points, |p| Some(p.generation_time.as_secs_f64()));
+    let memory_exponent = growth_exponent(&points, |p| p.peak_memory_bytes.map(|b| b as f64));
+    let size_exponent = growth_exponent(&points, |p| Some(p.html_bytes as f64));
+    for (metric, exponent) in [
+        ("generation time", time_exponent),
+        ("peak memory", memory_exponent),
+        ("HTML size", size_exponent),
+    ] {
+        match exponent {
+            Some(exponent) if exponent > SUPERLINEAR_EXPONENT => {
+                println!("   ❌ {} grows as n^{:.2}", metric, exponent);
+                failures.push(format!(
+                    "{} grows superlinearly (n^{:.2})",
+                    metric, exponent
+                ));
+            }
+            Some(exponent) => println!("   ✅ {} grows as n^{:.2}", metric, exponent),
+            None => println!("   ⚠️  {} growth could not be measured", metric),
+        }
+    }
+
+    let budget_bytes = html_budget_mb * 1024 * 1024;
+    if let Some(largest) = points.iter().max_by_key(|p| p.html_bytes) {
+        if largest.html_bytes as u64 > budget_bytes {
+            println!(
+                "   ❌ {} variables produced {:.2} MB, over the {} MB budget",
+                largest.variables,
+                largest.html_bytes as f64 / (1024.0 * 1024.0),
+                html_budget_mb
+            );
+            failures.push(format!(
+                "HTML for {} variables exceeds {} MB",
+                largest.variables, html_budget_mb
+            ));
+        } else {
+            println!(
+                "   ✅ Largest dashboard is within the {} MB budget",
+                html_budget_mb
+            );
+        }
+    }
+
+    let report_path = output_dir.join("hybrid_scalability.md");
+    let exponents = [time_exponent, memory_exponent, size_exponent];
+    std::fs::write(
+        &report_path,
+        render_scalability_table(&points, exponents, html_budget_mb, seed),
+    )?;
+    println!("\n📄 Scalability report: {}", report_path.display());
+
+    seed::announce(seed);
+    if failures.is_empty() {
+        println!("   ✅ SUCCESS: dashboard generation scales linearly");
+        Ok(())
+    } else {
+        println!("   ❌ FAILED: {} scalability problem(s)", failures.len());
+        Err(failures.join("; ").into())
+    }
+}
+
+/// Least-squares slope of `ln(metric)` against `ln(variables)`; 1.0 is linear
+fn growth_exponent<F>(points: &[ScalePoint], metric: F) -> Option<f64>
+where
+    F: Fn(&ScalePoint) -> Option<f64>,
+{
+    let samples: Vec<(f64, f64)> = points
+        .iter()
+        .filter_map(|p| {
+            metric(p)
+                .filter(|&value| value > 0.0)
+                .map(|value| ((p.variables as f64).ln(), value.ln()))
+        })
+        .collect();
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let n = samples.len() as f64;
+    let mean_x = samples.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = samples.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = samples
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = samples.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    Some(covariance / variance)
+}
+
+fn format_megabytes(bytes: Option<u64>) -> String {
+    match bytes {
+        Some(bytes) => format!("{:.2} MB", bytes as f64 / (1024.0 * 1024.0)),
+        None => "n/a".to_string(),
+    }
+}
+
+/// Render every measurement and the fitted growth exponents as markdown
+fn render_scalability_table(
+    points: &[ScalePoint],
+    [time_exponent, memory_exponent, size_exponent]: [Option<f64>; 3],
+    html_budget_mb: u64,
+    seed: u64,
+) -> String {
+    let exponent = |e: Option<f64>| e.map_or_else(|| "n/a".to_string(), |e| format!("{:.2}", e));
+
+    let mut table = String::new();
+    let _ = writeln!(table, "# Hybrid Dashboard Scalability\n");
+    let _ = writeln!(
+        table,
+        "- Seed: {seed} (reproduce with `{} {seed}`)",
+        seed::SEED_FLAG
+    );
+    let _ = writeln!(
+        table,
+        "- Threads: {THREADS}, variables per task: {VARIABLES_PER_TASK}, samples: {SAMPLES}"
+    );
+    let _ = writeln!(table, "- HTML budget: {html_budget_mb} MB");
+    let _ = writeln!(
+        table,
+        "- Growth exponents (limit {SUPERLINEAR_EXPONENT}): time {}, peak memory {}, HTML size {}\n",
+        exponent(time_exponent),
+        exponent(memory_exponent),
+        exponent(size_exponent)
+    );
+    let _ = writeln!(
+        table,
+        "| Variables | Generation (ms) | Peak memory (MB) | HTML (MB) | HTML bytes per variable |"
+    );
+    let _ = writeln!(table, "|---:|---:|---:|---:|---:|");
+    for point in points {
+        let _ = writeln!(
+            table,
+            "| {} | {:.2} | {} | {:.2} | {:.0} |",
+            point.variables,
+            point.generation_time.as_secs_f64() * 1000.0,
+            format_megabytes(point.peak_memory_bytes).trim_end_matches(" MB"),
+            point.html_bytes as f64 / (1024.0 * 1024.0),
+            point.html_bytes as f64 / point.variables as f64
+        );
+    }
+    table
+}
@@ -0,0 +1,258 @@
+//! Syntax check of the JavaScript embedded in generated HTML reports
+//!
+//! Inline `<script>` blocks are cut out the way an HTML parser does (the raw
+//! text ends at the first `</script`) and parsed with `oxc_parser`, as are
+//! inline event handler attributes such as `onclick="..."` outside scripts.
+//! Errors carry the line and column in the HTML document, not in the script.
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use std::fmt;
+use std::path::Path;
+
+/// An inline script block and where it starts in the document
+#[derive(Debug, Clone)]
+pub struct ScriptBlock<'a> {
+    /// Position among all `<script>` tags, starting at 0
+    pub index: usize,
+    /// 1-based document line of the first source character
+    pub line: usize,
+    /// 1-based document column of the first source character
+    pub column: usize,
+    pub source: &'a str,
+}
+
+/// A syntax error located in the HTML document
+#[derive(Debug, Clone)]
+pub struct ScriptSyntaxError {
+    /// Where the code came from, e.g. `script #2` or `onclick attribute`
+    pub context: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ScriptSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}:{}: {}",
+            self.context, self.line, self.column, self.message
+        )
+    }
+}
+
+/// Every inline JavaScript `<script>` block in `html`
+///
+/// External scripts (`src=`) and non-JavaScript types such as
+/// `application/json` are skipped.
+pub fn script_blocks(html: &str) -> Vec<ScriptBlock<'_>> {
+    raw_script_blocks(html)
+        .into_iter()
+        .filter_map(|(block, javascript)| javascript.then_some(block))
+        .collect()
+}
+
+/// Every `<script>` block, flagged with whether it holds inline JavaScript
+fn raw_script_blocks(html: &str) -> Vec<(ScriptBlock<'_>, bool)> {
+    let lower = html.to_ascii_lowercase();
+    let mut blocks = Vec::new();
+    let mut search_from = 0;
+    let mut index = 0;
+
+    while let Some(offset) = lower[search_from..].find("<script") {
+        let tag_start = search_from + offset;
+        let Some(tag_len) = lower[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &lower[tag_start..tag_start + tag_len];
+        let body_start = tag_start + tag_len + 1;
+        let body_end = lower[body_start..]
+            .find("</script")
+            .map_or(html.len(), |end| body_start + end);
+
+        let (line, column) = line_column(html, body_start);
+        let block = ScriptBlock {
+            index,
+            line,
+            column,
+            source: &html[body_start..body_end],
+        };
+        blocks.push((block, is_inline_javascript(tag)));
+        index += 1;
+        search_from = body_end;
+    }
+    blocks
+}
+
+/// Parse every inline script and event handler in the report at `path`,
+/// printing each syntax error with its line and column
+pub fn verify_report(path: &Path) -> Result<(), String> {
+    let html = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let errors = check_html(&html);
+
+    if errors.is_empty() {
+        println!(
+            "   ✅ JavaScript: {} script blocks parse in {}",
+            script_blocks(&html).len(),
+            path.display()
+        );
+        Ok(())
+    } else {
+        println!(
+            "   ❌ JavaScript: {} syntax error(s) in {}",
+            errors.len(),
+            path.display()
+        );
+        for error in &errors {
+            println!("      • {}", error);
+        }
+        Err(format!("{}: {}", path.display(), errors[0]))
+    }
+}
+
+/// Parse every inline script and event handler in `html` and report all
+/// syntax errors
+pub fn check_html(html: &str) -> Vec<ScriptSyntaxError> {
+    let mut errors = check_scripts(html);
+    errors.extend(check_event_handlers(html));
+    errors
+}
+
+/// Parse every inline script in `html` and report all syntax errors
+pub fn check_scripts(html: &str) -> Vec<ScriptSyntaxError> {
+    script_blocks(html)
+        .iter()
+        .flat_map(|block| {
+            parse_errors(
+                &format!("script #{}", block.index),
+                block.source,
+                (block.line, block.column),
+            )
+        })
+        .collect()
+}
+
+/// Parse every quoted `on*="..."` attribute outside script blocks
+///
+/// Attribute values are HTML-unescaped before parsing, as a browser does.
+pub fn check_event_handlers(html: &str) -> Vec<ScriptSyntaxError> {
+    let markup = without_script_bodies(html);
+    let mut errors = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(offset) = markup[search_from..].find("on") {
+        let name_start = search_from + offset;
+        search_from = name_start + 2;
+        if !markup[..name_start].ends_with(|c: char| c.is_ascii_whitespace()) {
+            continue;
+        }
+
+        let name_len = markup[name_start..]
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(markup.len() - name_start);
+        let name = &markup[name_start..name_start + name_len];
+        let rest = &markup[name_start + name_len..];
+        if name.len() <= 2 || !rest.starts_with("=\"") {
+            continue;
+        }
+
+        let value_start = name_start + name_len + 2;
+        let Some(value_len) = markup[value_start..].find('"') else {
+            break;
+        };
+        let handler = html_unescape(&markup[value_start..value_start + value_len]);
+        errors.extend(parse_errors(
+            &format!("{} attribute", name),
+            &handler,
+            line_column(html, value_start),
+        ));
+        search_from = value_start + value_len;
+    }
+    errors
+}
+
+/// Syntax errors in `source`, located relative to `origin` (line, column)
+fn parse_errors(context: &str, source: &str, origin: (usize, usize)) -> Vec<ScriptSyntaxError> {
+    let allocator = Allocator::default();
+    let parsed = Parser::new(&allocator, source, SourceType::cjs()).parse();
+
+    parsed
+        .errors
+        .iter()
+        .map(|error| {
+            let offset = error
+                .labels
+                .as_ref()
+                .and_then(|labels| labels.first())
+                .map_or(0, |label| label.offset());
+            let (line, column) = line_column(source, offset);
+            ScriptSyntaxError {
+                context: context.to_string(),
+                line: origin.0 + line - 1,
+                column: if line == 1 {
+                    origin.1 + column - 1
+                } else {
+                    column
+                },
+                message: error.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// `html` with every script body blanked out, keeping byte offsets intact
+fn without_script_bodies(html: &str) -> String {
+    let mut markup = html.to_string();
+    for (block, _) in raw_script_blocks(html) {
+        let start = block.source.as_ptr() as usize - html.as_ptr() as usize;
+        let blank: String = block
+            .source
+            .bytes()
+            .map(|b| if b == b'\n' { '\n' } else { ' ' })
+            .collect();
+        markup.replace_range(start..start + block.source.len(), &blank);
+    }
+    markup
+}
+
+fn html_unescape(value: &str) -> String {
+    value
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+fn is_inline_javascript(tag: &str) -> bool {
+    if tag.contains(" src=") {
+        return false;
+    }
+    match tag.split("type=").nth(1) {
+        None => true,
+        Some(rest) => {
+            let script_type = rest
+                .trim_start_matches(['"', '\''])
+                .split(['"', '\'', ' '])
+                .next()
+                .unwrap_or_default();
+            matches!(
+                script_type,
+                "" | "text/javascript" | "application/javascript" | "module"
+            )
+        }
+    }
+}
+
+/// 1-based line and column (in characters) of byte `offset` in `text`
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
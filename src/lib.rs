@@ -1,2 +1,20 @@
 //! MemScope Stress Test Library
-//! High-quality examples for memscope-rs v0.1.7
\ No newline at end of file
+//! High-quality examples for memscope-rs v0.1.7
+
+pub mod allocation;
+pub mod allocation_extensions;
+pub mod allocation_strategy;
+pub mod binary_mutation;
+pub mod cli;
+pub mod html_report;
+pub mod hybrid_data;
+pub mod js_syntax;
+pub mod placeholder_values;
+pub mod process_memory;
+pub mod report_data;
+pub mod report_roundtrip;
+pub mod resource_sampler;
+pub mod scenario;
+pub mod seed;
+pub mod task_registry;
+pub mod type_tree;
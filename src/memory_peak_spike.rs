@@ -0,0 +1,265 @@
+//! Memory Peak Detection with an Injected Spike
+//!
+//! The aggregated analysis exposes a `memory_peaks` list that stays empty in
+//! the checked-in output. This scenario verifies it against a known workload:
+//! 1. Hold a steady baseline of small tracked buffers with constant churn
+//! 2. Allocate one large block at a recorded timestamp and hold it
+//! 3. Free the block and return to the baseline
+//! 4. Check that `memory_peaks` reports a peak of the right magnitude inside
+//!    a tolerance window around the spike
+
+use memscope_rs::lockfree::aggregator::LockfreeAggregator;
+use memscope_rs::lockfree::analysis::MemoryPeak;
+use memscope_rs::lockfree::tracker::{
+    finalize_thread_tracker, init_thread_tracker, track_allocation_lockfree,
+    track_deallocation_lockfree, SamplingConfig,
+};
+use memscope_stress_test::seed;
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Number of small buffers kept alive as the baseline
+const BASELINE_BUFFERS: usize = 64;
+
+/// Size of each baseline buffer
+const BASELINE_BUFFER_SIZE: usize = 4096;
+
+/// Range the injected spike size is drawn from, in MB
+const SPIKE_SIZE_MB: RangeInclusive<usize> = 16..=48;
+
+/// How long the baseline runs before and after the spike
+const BASELINE_PHASE: Duration = Duration::from_millis(500);
+
+/// How long the spike allocation is held
+const SPIKE_HOLD: Duration = Duration::from_millis(300);
+
+/// Allowed distance between the reported peak and the spike window
+const TIMESTAMP_TOLERANCE: Duration = Duration::from_millis(100);
+
+/// Allowed relative error on the reported peak magnitude
+const MAGNITUDE_TOLERANCE: f64 = 0.10;
+
+/// Call stacks used for baseline and spike allocations
+const BASELINE_CALL_STACK: [usize; 2] = [0x1000, 0x2000];
+const SPIKE_CALL_STACK: [usize; 2] = [0x1000, 0x5000];
+
+/// Size of the injected spike and when it was live, in nanoseconds since the
+/// Unix epoch, the unit the lockfree tracker stamps its events with
+struct SpikeWindow {
+    size: usize,
+    start_ns: u64,
+    end_ns: u64,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("⛰️  Memory Peak Detection with an Injected Spike");
+    println!("===============================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let mut rng = seed::rng(seed);
+    let spike_size = rng.random_range(SPIKE_SIZE_MB) * 1024 * 1024;
+    println!(
+        "   📊 Baseline: {} x {} bytes, Spike: {:.1} MB for {:?}\n",
+        BASELINE_BUFFERS,
+        BASELINE_BUFFER_SIZE,
+        spike_size as f64 / (1024.0 * 1024.0),
+        SPIKE_HOLD
+    );
+
+    let output_dir = PathBuf::from("./Memoryanalysis/memory_peak_spike");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    // Run the workload on a dedicated thread so it owns exactly one tracker
+    let worker_dir = output_dir.clone();
+    let run_start_ns = unix_nanos();
+    let spike = thread::spawn(move || run_spike_workload(&worker_dir, spike_size, &mut rng))
+        .join()
+        .map_err(|_| "spike workload panicked")??;
+    let run_ns = run_start_ns..=unix_nanos();
+
+    println!(
+        "   📍 Spike window: {} ns → {} ns",
+        spike.start_ns, spike.end_ns
+    );
+
+    let aggregator = LockfreeAggregator::new(output_dir.clone());
+    let analysis = aggregator.aggregate_all_threads()?;
+    let json_path = output_dir.join("memory_peak_spike.json");
+    aggregator.export_analysis(&analysis, &json_path)?;
+
+    println!(
+        "\n📊 Reported memory peaks: {}",
+        analysis.memory_peaks.len()
+    );
+    for peak in &analysis.memory_peaks {
+        println!(
+            "   • {} bytes at {} ({} active allocations)",
+            peak.memory_usage, peak.timestamp, peak.active_allocations
+        );
+    }
+
+    verify_spike_detected(&analysis.memory_peaks, &spike, run_ns)
+}
+
+/// Baseline → spike → baseline, returning when the spike was live
+fn run_spike_workload(
+    output_dir: &Path,
+    spike_size: usize,
+    rng: &mut ChaCha8Rng,
+) -> Result<SpikeWindow, String> {
+    // Every allocation must be recorded for the magnitude check to hold
+    init_thread_tracker(output_dir, Some(SamplingConfig::high_precision()))
+        .map_err(|e| format!("Failed to init tracker: {}", e))?;
+
+    let mut baseline: Vec<Vec<u8>> = (0..BASELINE_BUFFERS)
+        .map(|_| vec![0u8; BASELINE_BUFFER_SIZE])
+        .collect();
+    for buffer in &baseline {
+        track_allocation_lockfree(
+            buffer.as_ptr() as usize,
+            BASELINE_BUFFER_SIZE,
+            &BASELINE_CALL_STACK,
+        )
+        .map_err(|e| format!("Failed to track allocation: {}", e))?;
+    }
+
+    run_baseline_phase(&mut baseline, rng)?;
+
+    let spike_block = vec![1u8; spike_size];
+    let start_ns = unix_nanos();
+    track_allocation_lockfree(spike_block.as_ptr() as usize, spike_size, &SPIKE_CALL_STACK)
+        .map_err(|e| format!("Failed to track spike allocation: {}", e))?;
+
+    thread::sleep(SPIKE_HOLD);
+
+    track_deallocation_lockfree(spike_block.as_ptr() as usize, &SPIKE_CALL_STACK)
+        .map_err(|e| format!("Failed to track spike deallocation: {}", e))?;
+    let end_ns = unix_nanos();
+    drop(spike_block);
+
+    run_baseline_phase(&mut baseline, rng)?;
+
+    for buffer in &baseline {
+        track_deallocation_lockfree(buffer.as_ptr() as usize, &BASELINE_CALL_STACK)
+            .map_err(|e| format!("Failed to track deallocation: {}", e))?;
+    }
+    finalize_thread_tracker().map_err(|e| format!("Failed to finalize tracker: {}", e))?;
+
+    Ok(SpikeWindow {
+        size: spike_size,
+        start_ns,
+        end_ns,
+    })
+}
+
+/// Replace randomly chosen baseline buffers one at a time for
+/// `BASELINE_PHASE`, keeping the live total constant
+fn run_baseline_phase(baseline: &mut [Vec<u8>], rng: &mut ChaCha8Rng) -> Result<(), String> {
+    let steps = 50;
+    for step in 0..steps {
+        let slot = rng.random_range(0..baseline.len());
+        let replacement = vec![step as u8; BASELINE_BUFFER_SIZE];
+        track_allocation_lockfree(
+            replacement.as_ptr() as usize,
+            BASELINE_BUFFER_SIZE,
+            &BASELINE_CALL_STACK,
+        )
+        .map_err(|e| format!("Failed to track allocation: {}", e))?;
+
+        let old = std::mem::replace(&mut baseline[slot], replacement);
+        track_deallocation_lockfree(old.as_ptr() as usize, &BASELINE_CALL_STACK)
+            .map_err(|e| format!("Failed to track deallocation: {}", e))?;
+
+        thread::sleep(BASELINE_PHASE / steps as u32);
+    }
+    Ok(())
+}
+
+/// Check that a reported peak matches the spike in time and magnitude, with
+/// every peak stamped inside `run_ns`
+fn verify_spike_detected(
+    peaks: &[MemoryPeak],
+    spike: &SpikeWindow,
+    run_ns: RangeInclusive<u64>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("\n🔍 Verifying Spike Detection:");
+
+    let tolerance_ns = TIMESTAMP_TOLERANCE.as_nanos() as u64;
+    let window_start = spike.start_ns.saturating_sub(tolerance_ns);
+    let window_end = spike.end_ns + tolerance_ns;
+
+    let expected = (BASELINE_BUFFERS * BASELINE_BUFFER_SIZE + spike.size) as f64;
+    let min_expected = expected * (1.0 - MAGNITUDE_TOLERANCE);
+    let max_expected = expected * (1.0 + MAGNITUDE_TOLERANCE);
+
+    println!(
+        "   📊 Expected peak: {:.0} bytes (±{:.0}%) within ±{:?} of the spike",
+        expected,
+        MAGNITUDE_TOLERANCE * 100.0,
+        TIMESTAMP_TOLERANCE
+    );
+
+    // A peak stamped outside the run means the tracker changed its time unit
+    if let Some(peak) = peaks.iter().find(|peak| !run_ns.contains(&peak.timestamp)) {
+        println!(
+            "   ❌ FAILED: Peak timestamp {} is outside the run",
+            peak.timestamp
+        );
+        return Err(format!(
+            "memory_peaks timestamp {} is not in nanoseconds since the Unix epoch \
+             (run lasted {} to {} ns)",
+            peak.timestamp,
+            run_ns.start(),
+            run_ns.end()
+        )
+        .into());
+    }
+
+    let in_window: Vec<_> = peaks
+        .iter()
+        .filter(|peak| (window_start..=window_end).contains(&peak.timestamp))
+        .collect();
+    println!("   📍 Peaks inside window: {}", in_window.len());
+
+    let matching = in_window.iter().find(|peak| {
+        let usage = peak.memory_usage as f64;
+        usage >= min_expected && usage <= max_expected
+    });
+
+    match matching {
+        Some(peak) => {
+            println!(
+                "   ✅ SUCCESS: Peak of {} bytes reported {:+.1} ms from spike start",
+                peak.memory_usage,
+                (peak.timestamp as f64 - spike.start_ns as f64) / 1_000_000.0
+            );
+            Ok(())
+        }
+        None => {
+            println!("   ❌ FAILED: No peak of the expected magnitude inside the spike window");
+            Err(format!(
+                "memory_peaks has no entry of {:.0}..{:.0} bytes between {} and {} ns",
+                min_expected, max_expected, window_start, window_end
+            )
+            .into())
+        }
+    }
+}
+
+/// Nanoseconds since the Unix epoch
+fn unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
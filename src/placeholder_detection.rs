@@ -0,0 +1,151 @@
+//! Placeholder Value Detection in Hybrid Dashboards
+//!
+//! This scenario renders the hybrid dashboard twice in every `RenderMode`:
+//! 1. From a seeded synthetic dataset
+//! 2. From a second dataset with a different shape whose names, thread ids,
+//!    task ids, sizes, counts and series share nothing with the first
+//!
+//! Any number, percentage, size or snake_case label shown at the same place
+//! in both renders cannot come from the data, so it is reported as a
+//! placeholder baked into the template (such as a hardcoded "15%" memory
+//! attribution).
+
+use memscope_rs::export::fixed_hybrid_template::{
+    FixedHybridTemplate, HybridAnalysisData, RenderMode,
+};
+use memscope_stress_test::hybrid_data::{render_modes, synthetic_hybrid_data, HybridDataShape};
+use memscope_stress_test::placeholder_values::{self, DisplayedValue};
+use memscope_stress_test::seed;
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+/// Shape of the first dataset
+const FIRST_SHAPE: HybridDataShape = HybridDataShape {
+    variables: 48,
+    threads: 4,
+    variables_per_task: 4,
+    samples: 20,
+};
+
+/// Shape of the second dataset, different in every dimension
+const SECOND_SHAPE: HybridDataShape = HybridDataShape {
+    variables: 131,
+    threads: 9,
+    variables_per_task: 7,
+    samples: 33,
+};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔍 Placeholder Value Detection in Hybrid Dashboards");
+    println!("===================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    let mut rng = seed::rng(seed);
+
+    let output_dir = PathBuf::from("./Memoryanalysis/placeholder_detection");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let first = synthetic_hybrid_data(FIRST_SHAPE, &mut rng);
+    let second =
+        placeholder_values::disjoint_dataset(&synthetic_hybrid_data(SECOND_SHAPE, &mut rng));
+    println!(
+        "   📦 Datasets: {} vs {} variables, {} vs {} threads",
+        first.variable_registry.len(),
+        second.variable_registry.len(),
+        first.thread_task_mapping.len(),
+        second.thread_task_mapping.len()
+    );
+
+    let mut placeholders: Vec<(&str, DisplayedValue)> = Vec::new();
+    for (label, render_mode) in render_modes() {
+        let first_html = render(&first, FIRST_SHAPE, render_mode())?;
+        let second_html = render(&second, SECOND_SHAPE, render_mode())?;
+        std::fs::write(
+            output_dir.join(format!("{}_first.html", label)),
+            format!("{}{}", first_html, seed::html_comment(seed)),
+        )?;
+        std::fs::write(
+            output_dir.join(format!("{}_second.html", label)),
+            format!("{}{}", second_html, seed::html_comment(seed)),
+        )?;
+
+        let shared = placeholder_values::shared_values(&first_html, &second_html);
+        if shared.is_empty() {
+            println!("   ✅ {}: every displayed value follows the data", label);
+        } else {
+            println!(
+                "   ❌ {}: {} value(s) identical across both datasets",
+                label,
+                shared.len()
+            );
+            for value in &shared {
+                println!("      • {}", value);
+            }
+        }
+        placeholders.extend(shared.into_iter().map(|value| (label, value)));
+    }
+
+    let report_path = output_dir.join("placeholder_detection.md");
+    std::fs::write(&report_path, render_placeholder_table(&placeholders, seed))?;
+    println!("\n📄 Placeholder report: {}", report_path.display());
+
+    seed::announce(seed);
+    if placeholders.is_empty() {
+        println!("   ✅ SUCCESS: no placeholder values in any render mode");
+        Ok(())
+    } else {
+        println!(
+            "   ❌ FAILED: {} placeholder value(s) across render modes",
+            placeholders.len()
+        );
+        Err(format!("{} placeholder values found", placeholders.len()).into())
+    }
+}
+
+fn render(
+    data: &HybridAnalysisData,
+    shape: HybridDataShape,
+    render_mode: RenderMode,
+) -> Result<String, Box<dyn std::error::Error>> {
+    FixedHybridTemplate::new(shape.threads, shape.variables / shape.variables_per_task)
+        .with_render_mode(render_mode)
+        .with_variable_details(true)
+        .with_enhanced_insights(true)
+        .generate_hybrid_dashboard(data)
+}
+
+/// Every placeholder with its render mode and location as a markdown table
+fn render_placeholder_table(placeholders: &[(&str, DisplayedValue)], seed: u64) -> String {
+    let mut table = String::new();
+    let _ = writeln!(table, "# Hybrid Dashboard Placeholder Values\n");
+    let _ = writeln!(
+        table,
+        "- Seed: {seed} (reproduce with `{} {seed}`)",
+        seed::SEED_FLAG
+    );
+    for (label, shape) in [("First", FIRST_SHAPE), ("Second", SECOND_SHAPE)] {
+        let _ = writeln!(
+            table,
+            "- {} dataset: {} variables, {} threads, {} variables per task, {} samples",
+            label, shape.variables, shape.threads, shape.variables_per_task, shape.samples
+        );
+    }
+    let _ = writeln!(table, "\n| Mode | Value | Location |");
+    let _ = writeln!(table, "|---|---|---|");
+    for (mode, value) in placeholders {
+        let _ = writeln!(
+            table,
+            "| {} | `{}` | `{}` |",
+            mode, value.value, value.context
+        );
+    }
+    if placeholders.is_empty() {
+        let _ = writeln!(table, "| - | none | - |");
+    }
+    table
+}
@@ -0,0 +1,303 @@
+//! Detection of placeholder values baked into dashboard templates
+//!
+//! The same dashboard is rendered from two datasets that share no names,
+//! thread ids, task ids, sizes or counts. Every number, percentage, size and
+//! snake_case label a user can see is collected from both renders together
+//! with where it appears; a value found at the same place in both renders did
+//! not come from the data and is reported as a placeholder.
+//!
+//! Values are taken from visible text (outside `<script>` and `<style>`) and,
+//! for percentages and sizes only, from string literals inside scripts, which
+//! is where text drawn on canvases (e.g. `'87%'`) lives. Other script strings
+//! are mostly ids, colours and keys rather than displayed values.
+
+use memscope_rs::export::fixed_hybrid_template::HybridAnalysisData;
+use scraper::{Html, Node};
+
+use std::collections::BTreeSet;
+use std::fmt;
+
+use crate::js_syntax;
+
+/// Units recognised after a number, longest first
+const UNITS: [&str; 7] = ["bytes", "KB", "MB", "GB", "ms", "B", "s"];
+
+/// Contexts whose value is a fixed scale rather than data, such as the
+/// "/ 100" next to the health score
+const FIXED_SCALE_CONTEXTS: [&str; 1] = ["span.score-unit"];
+
+/// A displayed value and where it appears
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DisplayedValue {
+    /// Element path such as `div.stat-card > div.stat-value`, or
+    /// `script #3 string` for a script string literal
+    pub context: String,
+    pub value: String,
+}
+
+impl fmt::Display for DisplayedValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} in {}", self.value, self.context)
+    }
+}
+
+/// Every number, percentage, size and snake_case label shown by `html`
+pub fn displayed_values(html: &str) -> BTreeSet<DisplayedValue> {
+    let document = Html::parse_document(html);
+    let mut values = BTreeSet::new();
+
+    for node in document.tree.nodes() {
+        let Node::Text(text) = node.value() else {
+            continue;
+        };
+        let ancestors: Vec<_> = node
+            .ancestors()
+            .filter_map(|ancestor| ancestor.value().as_element())
+            .collect();
+        if ancestors
+            .iter()
+            .any(|element| matches!(element.name(), "script" | "style"))
+        {
+            continue;
+        }
+
+        let context = ancestors
+            .iter()
+            .take(3)
+            .rev()
+            .map(|element| match (element.id(), element.classes().next()) {
+                (Some(id), _) => format!("{}#{}", element.name(), id),
+                (None, Some(class)) => format!("{}.{}", element.name(), class),
+                (None, None) => element.name().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" > ");
+        for value in value_tokens(text) {
+            values.insert(DisplayedValue {
+                context: context.clone(),
+                value,
+            });
+        }
+    }
+
+    for block in js_syntax::script_blocks(html) {
+        let context = format!("script #{} string", block.index);
+        for literal in string_literals(block.source) {
+            let with_unit = value_tokens(literal)
+                .into_iter()
+                .filter(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+                .filter(|v| !v.ends_with(|c: char| c.is_ascii_digit()));
+            for value in with_unit {
+                values.insert(DisplayedValue {
+                    context: context.clone(),
+                    value,
+                });
+            }
+        }
+    }
+    values
+}
+
+/// Values displayed at the same place in both renders, other than fixed
+/// scales
+pub fn shared_values(first_html: &str, second_html: &str) -> Vec<DisplayedValue> {
+    let first = displayed_values(first_html);
+    displayed_values(second_html)
+        .intersection(&first)
+        .filter(|shared| {
+            !FIXED_SCALE_CONTEXTS
+                .iter()
+                .any(|context| shared.context.ends_with(context))
+        })
+        .cloned()
+        .collect()
+}
+
+/// A variant of `data` sharing no displayed value with it
+///
+/// Every variable appears twice under suffixed names, so counts differ;
+/// thread and task ids move past the originals, and sizes, counts and
+/// series values are scaled and offset so that neither they nor totals and
+/// ratios derived from them repeat.
+pub fn disjoint_dataset(data: &HybridAnalysisData) -> HybridAnalysisData {
+    let thread_offset = data
+        .thread_task_mapping
+        .keys()
+        .chain(data.variable_registry.values().map(|v| &v.thread_id))
+        .max()
+        .copied()
+        .unwrap_or(0)
+        + 10;
+    let task_offset = data
+        .variable_registry
+        .values()
+        .filter_map(|v| v.task_id)
+        .chain(data.thread_task_mapping.values().flatten().copied())
+        .max()
+        .unwrap_or(0)
+        + 100;
+
+    let mut shifted = data.clone();
+    shifted.variable_registry = data
+        .variable_registry
+        .iter()
+        .flat_map(|(key, detail)| {
+            (0..2u64).map(move |copy| {
+                let mut detail = detail.clone();
+                detail.name = format!("{}_shifted_{}", detail.name, copy);
+                detail.thread_id += thread_offset;
+                detail.task_id = detail.task_id.map(|task| task + task_offset);
+                detail.allocation_count = detail.allocation_count * 3 + 7;
+                detail.memory_usage = detail.memory_usage * 3 + 1031 + copy * 64;
+                (format!("{}_shifted_{}", key, copy), detail)
+            })
+        })
+        .collect();
+    shifted.thread_task_mapping = data
+        .thread_task_mapping
+        .iter()
+        .map(|(thread, tasks)| {
+            (
+                thread + thread_offset,
+                tasks.iter().map(|task| task + task_offset).collect(),
+            )
+        })
+        .collect();
+    if let Some(analysis) = shifted.lockfree_analysis.as_mut() {
+        analysis.summary.total_allocations = analysis.summary.total_allocations * 6 + 7;
+        analysis.summary.peak_memory_usage = analysis.summary.peak_memory_usage * 6 + 1031;
+    }
+
+    let metrics = &mut shifted.performance_metrics;
+    for value in &mut metrics.cpu_usage {
+        *value = (*value * 0.37 + 13.0).min(100.0);
+    }
+    for series in [
+        &mut metrics.memory_usage,
+        &mut metrics.io_operations,
+        &mut metrics.network_bytes,
+    ] {
+        for value in series.iter_mut() {
+            *value = *value * 3 + 1031;
+        }
+    }
+    metrics.thread_cpu_breakdown = data
+        .performance_metrics
+        .thread_cpu_breakdown
+        .iter()
+        .map(|(thread, series)| {
+            (
+                thread + thread_offset,
+                series
+                    .iter()
+                    .map(|v| (v * 0.37 + 13.0).min(100.0))
+                    .collect(),
+            )
+        })
+        .collect();
+    metrics.thread_memory_breakdown = data
+        .performance_metrics
+        .thread_memory_breakdown
+        .iter()
+        .map(|(thread, series)| {
+            (
+                thread + thread_offset,
+                series.iter().map(|v| v * 3 + 1031).collect(),
+            )
+        })
+        .collect();
+    shifted
+}
+
+/// Numbers (with an optional `%` or size/time unit) and snake_case labels
+fn value_tokens(text: &str) -> Vec<String> {
+    let bytes = text.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        if !is_word(bytes[pos]) {
+            pos += 1;
+            continue;
+        }
+        let start = pos;
+        while pos < bytes.len() && is_word(bytes[pos]) {
+            pos += 1;
+        }
+        let word = &text[start..pos];
+
+        if word.bytes().all(|b| b.is_ascii_digit()) {
+            // Decimal part, then an optional percent sign or unit
+            let mut end = pos;
+            if bytes.get(end) == Some(&b'.') && bytes.get(end + 1).is_some_and(u8::is_ascii_digit) {
+                end += 1;
+                while end < bytes.len() && bytes[end].is_ascii_digit() {
+                    end += 1;
+                }
+            }
+            let rest = &text[end..];
+            let unit_start = rest.len() - rest.trim_start_matches(' ').len();
+            let suffix = if rest.starts_with('%') {
+                Some("%".len())
+            } else {
+                UNITS
+                    .iter()
+                    .find(|unit| {
+                        rest[unit_start..].starts_with(*unit)
+                            && !rest[unit_start + unit.len()..]
+                                .bytes()
+                                .next()
+                                .is_some_and(is_word)
+                    })
+                    .map(|unit| unit_start + unit.len())
+            };
+            end += suffix.unwrap_or(0);
+            tokens.push(text[start..end].to_string());
+            pos = end;
+        } else if word.contains('_') && word.bytes().any(|b| b.is_ascii_alphabetic()) {
+            tokens.push(word.to_string());
+        }
+        // Other words (`10px`, `Thread`) are neither values nor labels
+    }
+    tokens
+}
+
+/// Contents of the `'...'` and `"..."` literals in a script, skipping
+/// comments and template literals
+fn string_literals(source: &str) -> Vec<&str> {
+    let bytes = source.as_bytes();
+    let mut literals = Vec::new();
+    let mut pos = 0;
+
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'/' if bytes.get(pos + 1) == Some(&b'/') => {
+                pos = source[pos..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| pos + end);
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                pos = source[pos + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| pos + 2 + end + 2);
+            }
+            quote @ (b'\'' | b'"' | b'`') => {
+                let start = pos + 1;
+                pos = start;
+                while pos < bytes.len()
+                    && bytes[pos] != quote
+                    && (bytes[pos] != b'\n' || quote == b'`')
+                {
+                    pos += if bytes[pos] == b'\\' { 2 } else { 1 };
+                }
+                if quote != b'`' {
+                    literals.push(&source[start..pos.min(bytes.len())]);
+                }
+                pos += 1;
+            }
+            _ => pos += 1,
+        }
+    }
+    literals
+}
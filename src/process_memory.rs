@@ -0,0 +1,39 @@
+//! Resident and virtual memory readings from `/proc`
+//!
+//! Peak RSS is read from `VmHWM` in `/proc/self/status` and can be reset
+//! between measurements by writing `5` to `/proc/self/clear_refs`. Peak
+//! virtual size comes from `VmPeak`, for this or any other process. All are
+//! Linux-only; elsewhere every reading is `None`.
+
+use std::fs;
+
+const SELF_STATUS: &str = "/proc/self/status";
+
+/// Current resident set size in bytes
+pub fn current_rss_bytes() -> Option<u64> {
+    status_field_kb(SELF_STATUS, "VmRSS:").map(|kb| kb * 1024)
+}
+
+/// Peak resident set size in bytes since start or the last [`reset_peak_rss`]
+pub fn peak_rss_bytes() -> Option<u64> {
+    status_field_kb(SELF_STATUS, "VmHWM:").map(|kb| kb * 1024)
+}
+
+/// Peak virtual memory size in bytes of process `pid`, which also counts
+/// address space that was reserved but never touched
+pub fn peak_virtual_bytes_of(pid: u32) -> Option<u64> {
+    status_field_kb(&format!("/proc/{pid}/status"), "VmPeak:").map(|kb| kb * 1024)
+}
+
+/// Reset the peak RSS to the current RSS; returns whether the reset worked
+pub fn reset_peak_rss() -> bool {
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+fn status_field_kb(status_path: &str, field: &str) -> Option<u64> {
+    let status = fs::read_to_string(status_path).ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix(field))
+        .and_then(|value| value.trim().trim_end_matches("kB").trim().parse().ok())
+}
@@ -0,0 +1,988 @@
+//! Real Allocation Capture vs the Synthetic Binary Dashboard
+//!
+//! `binary_html` renders hand-written allocations with placeholder pointers
+//! and timestamps. This scenario runs the workloads those allocations stand
+//! for and records what actually happened to each tracked value:
+//! 1. `Vec<User>` read by concurrent lookups, mutated, cloned into a snapshot
+//! 2. `HashMap<String, Arc<Mutex<..>>>` shared with worker threads that clone
+//!    and lock its entries
+//! 3. `Rc<RefCell<..>>` cloned into game systems and borrowed every tick
+//! 4. `Box<dyn Trait>` dispatching events
+//! 5. A pinned, boxed future driven to completion by a tokio runtime
+//! 6. A scratch buffer that is freed and a `Vec<String>` that is leaked
+//!
+//! Every value is registered with `track_var!` where memscope can track its
+//! type; trait objects, which it cannot, are found among memscope's records by
+//! their heap address. The records memscope's global tracker collected for
+//! these values (pointers, sizes, threads, timestamps, borrow and clone
+//! counts) are exported as they are; only missing names and scopes are filled
+//! in. The report says whether memscope's `VariableRegistry` saw the same
+//! address. The allocations go through `export_to_binary` and
+//! `parse_binary_to_html_direct` next to the synthetic demo, and the two
+//! dashboards are compared section by section.
+//!
+//! The seed sizes the workloads; addresses and timestamps differ every run.
+
+use memscope_rs::core::types::AllocationInfo;
+use memscope_rs::export::binary;
+use memscope_rs::variable_registry::VariableRegistry;
+use memscope_rs::{get_global_tracker, init, track_var};
+use memscope_stress_test::allocation::{NANOS_PER_MS, PLACEHOLDER_TIMESTAMP};
+use memscope_stress_test::allocation_extensions::{self, TypeShape};
+use memscope_stress_test::{html_report, js_syntax, report_data, report_roundtrip, scenario, seed};
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+use serde_json::Value;
+
+use std::cell::{Ref, RefCell};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write as _;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Optional `AllocationInfo` fields compared between the dashboards
+const OPTIONAL_FIELDS: [&str; 18] = [
+    "stack_trace",
+    "borrow_info",
+    "clone_info",
+    "smart_pointer_info",
+    "memory_layout",
+    "generic_info",
+    "dynamic_type_info",
+    "runtime_state",
+    "stack_allocation",
+    "temporary_object",
+    "fragmentation_analysis",
+    "generic_instantiation",
+    "type_relationships",
+    "type_usage",
+    "function_call_tracking",
+    "lifecycle_tracking",
+    "access_tracking",
+    "drop_chain_analysis",
+];
+
+#[derive(Debug, Clone)]
+struct User {
+    id: u64,
+    name: String,
+    email: String,
+    age: u32,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    hits: u64,
+    bytes_served: u64,
+    payload: Vec<u8>,
+}
+
+#[derive(Debug)]
+struct GameState {
+    tick: u64,
+    positions: Vec<(f32, f32)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Event {
+    kind: usize,
+    payload: u64,
+}
+
+trait EventHandler {
+    fn handle(&self, event: &Event) -> u64;
+}
+
+/// Counts events per kind
+#[derive(Debug, Default)]
+struct MetricsHandler {
+    counts: [AtomicU64; 4],
+}
+
+impl EventHandler for MetricsHandler {
+    fn handle(&self, event: &Event) -> u64 {
+        self.counts[event.kind % 4].fetch_add(1, Ordering::Relaxed) + event.payload
+    }
+}
+
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Vec<u8>,
+}
+
+/// Workload sizes drawn from the seed
+#[derive(Debug, Clone, Copy)]
+struct WorkloadSizes {
+    users: usize,
+    readers: usize,
+    cache_entries: usize,
+    cache_workers: usize,
+    cache_ops: usize,
+    game_systems: usize,
+    game_ticks: usize,
+    events: usize,
+    future_chunks: usize,
+    scratch_bytes: usize,
+    leaked_strings: usize,
+}
+
+impl WorkloadSizes {
+    fn from_rng(rng: &mut ChaCha8Rng) -> Self {
+        WorkloadSizes {
+            users: rng.random_range(200..2000),
+            readers: rng.random_range(2..6),
+            cache_entries: rng.random_range(16..128),
+            cache_workers: rng.random_range(2..8),
+            cache_ops: rng.random_range(100..1000),
+            game_systems: rng.random_range(2..6),
+            game_ticks: rng.random_range(10..100),
+            events: rng.random_range(100..1000),
+            future_chunks: rng.random_range(4..32),
+            scratch_bytes: rng.random_range(4096..65536),
+            leaked_strings: rng.random_range(8..64),
+        }
+    }
+}
+
+/// A value a workload tracked and how to find memscope's record of it
+struct Tracked {
+    var_name: &'static str,
+    scope_name: &'static str,
+    /// Heap address of a value `track_var!` cannot take (trait objects),
+    /// whose record memscope keeps unnamed
+    heap_ptr: Option<usize>,
+    type_name: Option<String>,
+    /// Forgotten on purpose
+    leaked: bool,
+}
+
+impl Tracked {
+    /// A value registered with `track_var!`, found by its name
+    fn named(var_name: &'static str, scope_name: &'static str) -> Self {
+        Tracked {
+            var_name,
+            scope_name,
+            heap_ptr: None,
+            type_name: None,
+            leaked: false,
+        }
+    }
+
+    /// A value memscope only saw as an allocation at `heap_ptr`
+    fn by_address<T: ?Sized>(
+        var_name: &'static str,
+        scope_name: &'static str,
+        value: &T,
+        heap_ptr: usize,
+    ) -> Self {
+        Tracked {
+            heap_ptr: Some(heap_ptr),
+            type_name: Some(std::any::type_name_of_val(value).to_string()),
+            ..Tracked::named(var_name, scope_name)
+        }
+    }
+}
+
+/// What the registry recorded for a captured variable
+enum RegistryMatch {
+    SameAddress,
+    OtherAddress(usize),
+    NotRegistered,
+}
+
+/// Figures read back from one dashboard's embedded data
+struct DashboardSummary {
+    allocations: usize,
+    total_bytes: u64,
+    threads: usize,
+    types: usize,
+    deallocated: usize,
+    leaked: usize,
+    placeholder_timestamps: usize,
+    timestamp_span_ms: u64,
+    /// Allocations with each of [`OPTIONAL_FIELDS`] set
+    field_coverage: Vec<usize>,
+    missing_sections: Vec<&'static str>,
+    script_errors: usize,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔬 Real Allocation Capture vs the Synthetic Binary Dashboard");
+    println!("============================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+    let mut rng = seed::rng(seed);
+
+    let output_dir = PathBuf::from("./Memoryanalysis/real_capture");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    init();
+    let sizes = WorkloadSizes::from_rng(&mut rng);
+    println!("   ⚙️  Workload sizes: {sizes:?}");
+
+    let run_start = now_ns();
+    let mut tracked = Vec::new();
+
+    // Main-thread values stay alive until the export, like a running service
+    let (user_data, user_values) = user_service(&sizes);
+    tracked.extend(user_values);
+    let (cache_storage, cache_value) = cache_module(&sizes)?;
+    tracked.push(cache_value);
+    tracked.push(spawn_workload("game_loop", move || game_engine(&sizes))?);
+    tracked.push(spawn_workload("event_loop", move || event_system(&sizes))?);
+    tracked.push(spawn_workload("async_runtime", move || {
+        async_handler(&sizes)
+    })?);
+    tracked.push(temporary_processing(&sizes));
+    tracked.push(buggy_function(&sizes));
+    let run_end = now_ns();
+
+    let (captured, missing) = memscope_records(&tracked)?;
+    println!(
+        "   📦 memscope recorded {} of {} tracked values:",
+        captured.len(),
+        tracked.len()
+    );
+    let registry = registry_matches(&captured);
+    for (allocation, registered) in captured.iter().zip(&registry) {
+        println!(
+            "      • {} ({}) on {}: {} bytes at {:#x}, memscope: {}",
+            allocation.var_name.as_deref().unwrap_or("?"),
+            allocation.type_name.as_deref().unwrap_or("?"),
+            allocation.thread_id,
+            allocation.size,
+            allocation.ptr,
+            registry_label(registered)
+        );
+    }
+
+    let mut problems: Vec<String> = missing
+        .iter()
+        .map(|name| format!("memscope has no record of {name}"))
+        .collect();
+    problems.extend(check_captured(&captured, run_start, run_end));
+
+    println!("\n💾 Exporting real allocations...");
+    let real_html = output_dir.join("real_capture.html");
+    problems.extend(export_and_convert(
+        &captured,
+        &output_dir.join("real_capture.memscope"),
+        &real_html,
+        "Real Allocation Capture",
+    )?);
+
+    println!("\n💾 Exporting the synthetic demo...");
+    let synthetic = synthetic_allocations()?;
+    let synthetic_html = output_dir.join("synthetic.html");
+    export_and_convert(
+        &synthetic,
+        &output_dir.join("synthetic.memscope"),
+        &synthetic_html,
+        "Synthetic Comprehensive Demo",
+    )?;
+
+    let real_summary = summarize_dashboard(&std::fs::read_to_string(&real_html)?)?;
+    let synthetic_summary = summarize_dashboard(&std::fs::read_to_string(&synthetic_html)?)?;
+    if real_summary.placeholder_timestamps > 0 {
+        problems.push(format!(
+            "{} allocation(s) in the real dashboard carry the placeholder timestamp {}",
+            real_summary.placeholder_timestamps, PLACEHOLDER_TIMESTAMP
+        ));
+    }
+    for id in &real_summary.missing_sections {
+        problems.push(format!("real dashboard is missing section #{id}"));
+    }
+    if real_summary.script_errors > 0 {
+        problems.push(format!(
+            "real dashboard has {} JavaScript syntax error(s)",
+            real_summary.script_errors
+        ));
+    }
+
+    let gaps = coverage_gaps(&synthetic_summary, &real_summary);
+    println!("\n📊 Dashboard comparison (synthetic → real):");
+    println!(
+        "   Allocations: {} → {}, threads: {} → {}, types: {} → {}",
+        synthetic_summary.allocations,
+        real_summary.allocations,
+        synthetic_summary.threads,
+        real_summary.threads,
+        synthetic_summary.types,
+        real_summary.types
+    );
+    if gaps.is_empty() {
+        println!("   ✅ Every section filled by the synthetic data is filled by real data");
+    } else {
+        println!("   ⚠️  Empty with real data: {}", gaps.join(", "));
+    }
+
+    let report_path = output_dir.join("real_capture.md");
+    std::fs::write(
+        &report_path,
+        render_report(
+            seed,
+            &captured,
+            &registry,
+            &synthetic_summary,
+            &real_summary,
+            &gaps,
+            &problems,
+        ),
+    )?;
+    println!("\n📄 Comparison report: {}", report_path.display());
+
+    // The main-thread values are dropped only after the export
+    drop(user_data);
+    drop(cache_storage);
+
+    if problems.is_empty() {
+        println!(
+            "   ✅ SUCCESS: {} real allocations exported and rendered with real pointers, threads and timestamps",
+            captured.len()
+        );
+        seed::announce(seed);
+        Ok(())
+    } else {
+        for problem in &problems {
+            println!("   ❌ {problem}");
+        }
+        println!(
+            "   ❌ FAILED: {} problem(s) in the real capture",
+            problems.len()
+        );
+        seed::announce(seed);
+        Err(problems[0].clone().into())
+    }
+}
+
+fn now_ns() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Run a workload on its own named thread
+fn spawn_workload<F>(name: &str, workload: F) -> Result<Tracked, String>
+where
+    F: FnOnce() -> Result<Tracked, String> + Send + 'static,
+{
+    std::thread::Builder::new()
+        .name(name.to_string())
+        .spawn(workload)
+        .map_err(|e| format!("cannot spawn {name}: {e}"))?
+        .join()
+        .map_err(|_| format!("{name} panicked"))?
+}
+
+/// `Vec<User>` read by concurrent lookups, mutated in place and cloned
+fn user_service(sizes: &WorkloadSizes) -> (Vec<User>, Vec<Tracked>) {
+    let mut user_data: Vec<User> = (0..sizes.users as u64)
+        .map(|id| User {
+            id,
+            name: format!("user_{id}"),
+            email: format!("user_{id}@example.com"),
+            age: 18 + (id % 60) as u32,
+        })
+        .collect();
+    track_var!(user_data);
+
+    let lookups = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for reader in 0..sizes.readers {
+            let users = &user_data;
+            let lookups = &lookups;
+            scope.spawn(move || {
+                for id in (reader..users.len()).step_by(sizes.readers * 7) {
+                    if users.iter().any(|user| user.id == id as u64) {
+                        lookups.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+    for user in user_data.iter_mut() {
+        user.age += 1;
+    }
+    user_data.sort_by_key(|user| user.age);
+
+    let user_snapshot = user_data.clone();
+    track_var!(user_snapshot);
+    let adults = user_snapshot
+        .iter()
+        .filter(|user| user.age >= 21 && user.email.starts_with(user.name.as_str()))
+        .count();
+    drop(user_snapshot);
+    println!(
+        "   👥 user_service: {} lookups, {adults} adults in the snapshot",
+        lookups.load(Ordering::Relaxed)
+    );
+
+    (
+        user_data,
+        vec![
+            Tracked::named("user_data", "user_service"),
+            Tracked::named("user_snapshot", "user_service"),
+        ],
+    )
+}
+
+type Cache = HashMap<String, Arc<Mutex<CacheEntry>>>;
+
+/// A map of shared entries that worker threads look up, clone and lock
+fn cache_module(sizes: &WorkloadSizes) -> Result<(Cache, Tracked), String> {
+    let mut cache_storage: Cache = HashMap::with_capacity(sizes.cache_entries);
+    for idx in 0..sizes.cache_entries {
+        let key = format!("key_{idx}");
+        let entry = CacheEntry {
+            hits: 0,
+            bytes_served: 0,
+            payload: vec![idx as u8; 256],
+        };
+        cache_storage.insert(key, Arc::new(Mutex::new(entry)));
+    }
+    track_var!(cache_storage);
+
+    std::thread::scope(|scope| -> Result<(), String> {
+        let mut workers = Vec::new();
+        for worker in 0..sizes.cache_workers {
+            let cache = &cache_storage;
+            let handle = std::thread::Builder::new()
+                .name(format!("cache_worker_{worker}"))
+                .spawn_scoped(scope, move || {
+                    let mut held = Vec::new();
+                    for op in 0..sizes.cache_ops {
+                        let key = format!("key_{}", (op * 31 + worker) % sizes.cache_entries);
+                        let Some(entry) = cache.get(&key) else {
+                            continue;
+                        };
+                        let entry = Arc::clone(entry);
+                        if let Ok(mut guard) = entry.lock() {
+                            guard.hits += 1;
+                            guard.bytes_served += guard.payload.len() as u64;
+                        }
+                        held.push(entry);
+                    }
+                    held.len()
+                })
+                .map_err(|e| format!("cannot spawn cache_worker_{worker}: {e}"))?;
+            workers.push(handle);
+        }
+        for handle in workers {
+            handle
+                .join()
+                .map_err(|_| "a cache worker panicked".to_string())?;
+        }
+        Ok(())
+    })?;
+
+    let hits: u64 = cache_storage
+        .values()
+        .filter_map(|entry| entry.lock().ok().map(|entry| entry.hits))
+        .sum();
+    println!(
+        "   🗄️  cache_module: {hits} hits from {} workers",
+        sizes.cache_workers
+    );
+    Ok((
+        cache_storage,
+        Tracked::named("cache_storage", "cache_module"),
+    ))
+}
+
+/// `Rc<RefCell<..>>` shared by game systems, borrowed every tick
+fn game_engine(sizes: &WorkloadSizes) -> Result<Tracked, String> {
+    let game_state = Rc::new(RefCell::new(GameState {
+        tick: 0,
+        positions: vec![(0.0, 0.0); 64],
+    }));
+    track_var!(game_state);
+
+    let systems: Vec<Rc<RefCell<GameState>>> = (0..sizes.game_systems)
+        .map(|_| Rc::clone(&game_state))
+        .collect();
+    for _ in 0..sizes.game_ticks {
+        for system in &systems {
+            let state = system.try_borrow().map_err(|e| e.to_string())?;
+            let _ = state.positions.iter().map(|(x, y)| x + y).sum::<f32>();
+        }
+        let mut state = game_state.try_borrow_mut().map_err(|e| e.to_string())?;
+        state.tick += 1;
+        for position in state.positions.iter_mut() {
+            position.0 += 1.0;
+        }
+    }
+
+    // Every system reads the state at once for the frame snapshot
+    let frame: Vec<Ref<GameState>> = systems
+        .iter()
+        .map(|system| system.try_borrow().map_err(|e| e.to_string()))
+        .collect::<Result<_, _>>()?;
+    let tick = frame.first().map(|state| state.tick).unwrap_or_default();
+    drop(frame);
+
+    drop(systems);
+    drop(game_state);
+    println!(
+        "   🎮 game_loop: {tick} ticks over {} systems",
+        sizes.game_systems
+    );
+    Ok(Tracked::named("game_state", "game_engine"))
+}
+
+/// `Box<dyn Trait>` dispatching a stream of events
+fn event_system(sizes: &WorkloadSizes) -> Result<Tracked, String> {
+    let event_handler: Box<dyn EventHandler + Send + Sync> = Box::new(MetricsHandler::default());
+    let tracked = Tracked::by_address(
+        "event_handler",
+        "event_system",
+        &event_handler,
+        &*event_handler as *const (dyn EventHandler + Send + Sync) as *const () as usize,
+    );
+
+    let mut checksum = 0u64;
+    for idx in 0..sizes.events {
+        let event = Event {
+            kind: idx % 7,
+            payload: idx as u64,
+        };
+        checksum = checksum.wrapping_add(event_handler.handle(&event));
+    }
+
+    drop(event_handler);
+    println!(
+        "   📨 event_loop: {} events dispatched (checksum {checksum})",
+        sizes.events
+    );
+    Ok(tracked)
+}
+
+/// A pinned, boxed future that yields between chunks of its response
+fn async_handler(sizes: &WorkloadSizes) -> Result<Tracked, String> {
+    let chunks = sizes.future_chunks;
+    let mut future_state: Pin<Box<dyn Future<Output = Result<Response, String>>>> =
+        Box::pin(async move {
+            let mut body = Vec::new();
+            for chunk in 0..chunks {
+                body.extend_from_slice(&[chunk as u8; 64]);
+                tokio::task::yield_now().await;
+            }
+            Ok(Response { status: 200, body })
+        });
+    let tracked = Tracked::by_address(
+        "future_state",
+        "async_handler",
+        &future_state,
+        &*future_state as *const dyn Future<Output = Result<Response, String>> as *const ()
+            as usize,
+    );
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| format!("cannot start the async runtime: {e}"))?;
+    let response = runtime.block_on(future_state.as_mut())?;
+
+    drop(future_state);
+    println!(
+        "   ⏳ async_runtime: status {} with {} bytes after {} polls",
+        response.status,
+        response.body.len(),
+        chunks + 1
+    );
+    Ok(tracked)
+}
+
+/// A scratch buffer freed once its checksum is taken
+fn temporary_processing(sizes: &WorkloadSizes) -> Tracked {
+    let temp_buffer: Vec<u8> = (0..sizes.scratch_bytes).map(|idx| idx as u8).collect();
+    track_var!(temp_buffer);
+    let checksum: u64 = temp_buffer.iter().map(|&byte| byte as u64).sum();
+    drop(temp_buffer);
+    println!("   🧹 temp_buffer: checksum {checksum}, freed");
+    Tracked::named("temp_buffer", "temporary_processing")
+}
+
+/// A `Vec<String>` that is never freed
+fn buggy_function(sizes: &WorkloadSizes) -> Tracked {
+    let leaked_data: Vec<String> = (0..sizes.leaked_strings)
+        .map(|idx| format!("leaked_{idx}"))
+        .collect();
+    track_var!(leaked_data);
+    std::mem::forget(leaked_data);
+    Tracked {
+        leaked: true,
+        ..Tracked::named("leaked_data", "buggy_function")
+    }
+}
+
+/// memscope's record of every tracked value, plus the values it has none for
+///
+/// Several records can match when an address or name is reused; the latest
+/// allocation wins, and of two copies of it the one that saw the free.
+fn memscope_records(tracked: &[Tracked]) -> Result<(Vec<AllocationInfo>, Vec<String>), String> {
+    let tracker = get_global_tracker();
+    let mut records = tracker
+        .get_allocation_history()
+        .map_err(|e| format!("cannot read memscope's allocation history: {e}"))?;
+    records.extend(
+        tracker
+            .get_active_allocations()
+            .map_err(|e| format!("cannot read memscope's active allocations: {e}"))?,
+    );
+
+    let mut captured = Vec::new();
+    let mut missing = Vec::new();
+    for value in tracked {
+        let record = records
+            .iter()
+            .filter(|record| match value.heap_ptr {
+                Some(ptr) => record.ptr == ptr,
+                None => record.var_name.as_deref() == Some(value.var_name),
+            })
+            .max_by_key(|record| (record.timestamp_alloc, record.timestamp_dealloc.is_some()));
+        let Some(record) = record else {
+            missing.push(value.var_name.to_string());
+            continue;
+        };
+
+        let mut record = record.clone();
+        // Values `track_var!` cannot take reach memscope unnamed
+        record
+            .var_name
+            .get_or_insert_with(|| value.var_name.to_string());
+        if let Some(type_name) = &value.type_name {
+            record.type_name.get_or_insert_with(|| type_name.clone());
+        }
+        record
+            .scope_name
+            .get_or_insert_with(|| value.scope_name.to_string());
+        // memscope only sees that the value was never freed; the workload
+        // knows it was forgotten on purpose
+        record.is_leaked |= value.leaked;
+        captured.push(record);
+    }
+    Ok((captured, missing))
+}
+
+/// Whether memscope's registry holds each captured variable at the same address
+fn registry_matches(captured: &[AllocationInfo]) -> Vec<RegistryMatch> {
+    let registry = VariableRegistry::get_all_variables();
+    captured
+        .iter()
+        .map(|allocation| {
+            let registered: Vec<usize> = registry
+                .iter()
+                .filter(|(_, variable)| {
+                    allocation.var_name.as_deref() == Some(variable.var_name.as_str())
+                })
+                .map(|(&addr, _)| addr)
+                .collect();
+            if registered.contains(&allocation.ptr) {
+                RegistryMatch::SameAddress
+            } else if let Some(&addr) = registered.first() {
+                RegistryMatch::OtherAddress(addr)
+            } else {
+                RegistryMatch::NotRegistered
+            }
+        })
+        .collect()
+}
+
+fn registry_label(registered: &RegistryMatch) -> String {
+    match registered {
+        RegistryMatch::SameAddress => "same address".to_string(),
+        RegistryMatch::OtherAddress(addr) => format!("registered at {addr:#x}"),
+        RegistryMatch::NotRegistered => "not registered".to_string(),
+    }
+}
+
+/// Captured records must be real: non-null pointers that no two live values
+/// share at once, timestamps inside the run and frees after allocations
+fn check_captured(captured: &[AllocationInfo], run_start: u64, run_end: u64) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (idx, allocation) in captured.iter().enumerate() {
+        let name = allocation.var_name.as_deref().unwrap_or("?");
+        if allocation.ptr == 0 {
+            problems.push(format!("{name} has a null pointer"));
+        }
+        // A freed address may be handed out again, so only overlapping
+        // lifetimes have to use distinct pointers
+        for other in &captured[idx + 1..] {
+            if other.ptr == allocation.ptr && lifetimes_overlap(allocation, other) {
+                problems.push(format!(
+                    "{name} and {} are live at {:#x} at the same time",
+                    other.var_name.as_deref().unwrap_or("?"),
+                    allocation.ptr
+                ));
+            }
+        }
+        if !(run_start..=run_end).contains(&allocation.timestamp_alloc) {
+            problems.push(format!(
+                "{name} was allocated at {} outside the run",
+                allocation.timestamp_alloc
+            ));
+        }
+        if let Some(dealloc) = allocation.timestamp_dealloc {
+            if dealloc < allocation.timestamp_alloc || dealloc > run_end {
+                problems.push(format!("{name} was freed at {dealloc} out of order"));
+            }
+        }
+    }
+    problems
+}
+
+/// Whether two allocations were live at the same time; a missing free means
+/// live until the end of the run
+fn lifetimes_overlap(a: &AllocationInfo, b: &AllocationInfo) -> bool {
+    let a_end = a.timestamp_dealloc.unwrap_or(u64::MAX);
+    let b_end = b.timestamp_dealloc.unwrap_or(u64::MAX);
+    a.timestamp_alloc < b_end && b.timestamp_alloc < a_end
+}
+
+/// Export, read back and convert, returning round-trip problems
+fn export_and_convert(
+    allocations: &[AllocationInfo],
+    binary_path: &Path,
+    html_path: &Path,
+    title: &str,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut problems = Vec::new();
+    binary::export_to_binary(allocations, binary_path)?;
+    println!(
+        "   Binary: {} ({} bytes)",
+        binary_path.display(),
+        std::fs::metadata(binary_path)?.len()
+    );
+
+    let read_back = binary::BinaryReader::new(binary_path)?.read_all()?;
+    let mismatches = report_roundtrip::diff_read_back(allocations, &read_back);
+    if let Err(e) =
+        report_roundtrip::report_mismatches("allocations read back", allocations.len(), &mismatches)
+    {
+        problems.push(e);
+    }
+    let lost = report_roundtrip::diff_lost_extensions(allocations, &read_back);
+    report_roundtrip::report_lost_extensions(allocations.len(), &lost);
+
+    binary::parse_binary_to_html_direct(binary_path, html_path, title)?;
+    println!(
+        "   HTML:   {} ({} bytes)",
+        html_path.display(),
+        std::fs::metadata(html_path)?.len()
+    );
+    let content = std::fs::read_to_string(html_path)?;
+    let embedded = report_roundtrip::embedded_allocations(&content)?;
+    let mismatches = report_roundtrip::diff_allocations(allocations, &embedded);
+    if let Err(e) =
+        report_roundtrip::report_mismatches("embedded allocations", allocations.len(), &mismatches)
+    {
+        problems.push(e);
+    }
+    Ok(problems)
+}
+
+/// The comprehensive demo as `binary_html` renders it, without seeded variation
+fn synthetic_allocations() -> Result<Vec<AllocationInfo>, String> {
+    let mut allocations = scenario::from_toml(scenario::COMPREHENSIVE_DEMO)?.allocations()?;
+    for allocation in allocations.iter_mut() {
+        let shape = TypeShape::parse(allocation.type_name.as_deref().unwrap_or_default())?;
+        allocation_extensions::populate(allocation, &shape);
+    }
+    Ok(allocations)
+}
+
+fn summarize_dashboard(html: &str) -> Result<DashboardSummary, String> {
+    let data = report_data::embedded_object(html, report_data::ANALYSIS_DATA)?;
+    let allocations = data["allocations"].as_array().cloned().unwrap_or_default();
+    let distinct = |field: &str| {
+        allocations
+            .iter()
+            .filter_map(|a| a[field].as_str())
+            .collect::<BTreeSet<_>>()
+            .len()
+    };
+    let timestamps: Vec<u64> = allocations
+        .iter()
+        .flat_map(|a| [&a["timestamp_alloc"], &a["timestamp_dealloc"]])
+        .filter_map(Value::as_u64)
+        .collect();
+    let span = timestamps.iter().max().zip(timestamps.iter().min());
+
+    Ok(DashboardSummary {
+        allocations: allocations.len(),
+        total_bytes: allocations.iter().filter_map(|a| a["size"].as_u64()).sum(),
+        threads: distinct("thread_id"),
+        types: distinct("type_name"),
+        deallocated: allocations
+            .iter()
+            .filter(|a| !a["timestamp_dealloc"].is_null())
+            .count(),
+        leaked: allocations
+            .iter()
+            .filter(|a| a["is_leaked"].as_bool() == Some(true))
+            .count(),
+        placeholder_timestamps: allocations
+            .iter()
+            .filter(|a| a["timestamp_alloc"].as_u64() == Some(PLACEHOLDER_TIMESTAMP))
+            .count(),
+        timestamp_span_ms: span.map_or(0, |(max, min)| (max - min) / NANOS_PER_MS),
+        field_coverage: OPTIONAL_FIELDS
+            .iter()
+            .map(|field| allocations.iter().filter(|a| !a[*field].is_null()).count())
+            .collect(),
+        missing_sections: html_report::HtmlReport::parse(html)
+            .missing_ids(&html_report::BINARY_DASHBOARD_SECTION_IDS),
+        script_errors: js_syntax::check_html(html).len(),
+    })
+}
+
+/// Fields the synthetic dashboard shows that no real allocation fills
+fn coverage_gaps(synthetic: &DashboardSummary, real: &DashboardSummary) -> Vec<&'static str> {
+    OPTIONAL_FIELDS
+        .iter()
+        .zip(synthetic.field_coverage.iter().zip(&real.field_coverage))
+        .filter(|(_, (&synthetic, &real))| synthetic > 0 && real == 0)
+        .map(|(field, _)| *field)
+        .collect()
+}
+
+fn render_report(
+    seed: u64,
+    captured: &[AllocationInfo],
+    registry: &[RegistryMatch],
+    synthetic: &DashboardSummary,
+    real: &DashboardSummary,
+    gaps: &[&str],
+    problems: &[String],
+) -> String {
+    let mut report = String::new();
+    let _ = writeln!(report, "# Real Allocation Capture vs Synthetic Dashboard\n");
+    let _ = writeln!(
+        report,
+        "- Seed: {seed} (reproduce the workload sizes with `{} {seed}`)",
+        seed::SEED_FLAG
+    );
+    let _ = writeln!(
+        report,
+        "- Registered with memscope at the captured address: {} of {}",
+        registry
+            .iter()
+            .filter(|r| matches!(r, RegistryMatch::SameAddress))
+            .count(),
+        captured.len()
+    );
+    let _ = writeln!(
+        report,
+        "- Status: {}\n",
+        if problems.is_empty() {
+            "✅ real data exported and rendered"
+        } else {
+            "❌ problems found"
+        }
+    );
+
+    let _ = writeln!(report, "## Captured Allocations\n");
+    let _ = writeln!(
+        report,
+        "| Variable | Type | Thread | Pointer | Bytes | Lifetime (ms) | Borrows | Clones | memscope |"
+    );
+    let _ = writeln!(report, "|---|---|---|---|---:|---:|---:|---:|---|");
+    for (allocation, registered) in captured.iter().zip(registry) {
+        let _ = writeln!(
+            report,
+            "| {} | `{}` | {} | {:#x} | {} | {} | {} | {} | {} |",
+            allocation.var_name.as_deref().unwrap_or("?"),
+            allocation.type_name.as_deref().unwrap_or("?"),
+            allocation.thread_id,
+            allocation.ptr,
+            allocation.size,
+            if allocation.is_leaked {
+                "leaked".to_string()
+            } else {
+                allocation
+                    .lifetime_ms
+                    .map_or_else(|| "live".to_string(), |ms| ms.to_string())
+            },
+            allocation.borrow_count,
+            allocation
+                .clone_info
+                .as_ref()
+                .map_or(0, |clones| clones.clone_count),
+            registry_label(registered)
+        );
+    }
+
+    let _ = writeln!(report, "\n## Dashboards\n");
+    let _ = writeln!(report, "| Metric | Synthetic | Real |");
+    let _ = writeln!(report, "|---|---:|---:|");
+    let rows = [
+        ("Allocations", synthetic.allocations, real.allocations),
+        (
+            "Total bytes",
+            synthetic.total_bytes as usize,
+            real.total_bytes as usize,
+        ),
+        ("Threads", synthetic.threads, real.threads),
+        ("Types", synthetic.types, real.types),
+        ("Deallocated", synthetic.deallocated, real.deallocated),
+        ("Leaked", synthetic.leaked, real.leaked),
+        (
+            "Placeholder timestamps",
+            synthetic.placeholder_timestamps,
+            real.placeholder_timestamps,
+        ),
+        (
+            "Timestamp span (ms)",
+            synthetic.timestamp_span_ms as usize,
+            real.timestamp_span_ms as usize,
+        ),
+        (
+            "Missing sections",
+            synthetic.missing_sections.len(),
+            real.missing_sections.len(),
+        ),
+        (
+            "JavaScript syntax errors",
+            synthetic.script_errors,
+            real.script_errors,
+        ),
+    ];
+    for (metric, synthetic, real) in rows {
+        let _ = writeln!(report, "| {metric} | {synthetic} | {real} |");
+    }
+    for (field, (synthetic, real)) in OPTIONAL_FIELDS
+        .iter()
+        .zip(synthetic.field_coverage.iter().zip(&real.field_coverage))
+    {
+        let _ = writeln!(report, "| With `{field}` | {synthetic} | {real} |");
+    }
+
+    if !gaps.is_empty() {
+        let _ = writeln!(report, "\n## Sections Only the Synthetic Data Fills\n");
+        let _ = writeln!(
+            report,
+            "Real capture leaves these fields empty, so the dashboard shows nothing where the demo shows data:\n"
+        );
+        for field in gaps {
+            let _ = writeln!(report, "- `{field}`");
+        }
+    }
+    if !problems.is_empty() {
+        let _ = writeln!(report, "\n## Problems\n");
+        for problem in problems {
+            let _ = writeln!(report, "- {problem}");
+        }
+    }
+    report
+}
@@ -0,0 +1,279 @@
+//! Render-Mode Matrix for FixedHybridTemplate Dashboards
+//!
+//! This scenario renders one seeded `HybridAnalysisData` through every
+//! combination of:
+//! 1. `RenderMode` (Comprehensive, ThreadFocused, VariableDetailed)
+//! 2. Variable details on/off and enhanced insights on/off
+//! 3. A range of `FixedHybridTemplate::new` parameters
+//!
+//! Every output is validated (complete document, embedded JavaScript that
+//! parses, `window.DASHBOARD_DATA` consistent with the input), and its size and
+//! generation time are recorded, so a template change that breaks a single
+//! mode is caught.
+
+use memscope_rs::export::fixed_hybrid_template::{FixedHybridTemplate, HybridAnalysisData};
+use memscope_stress_test::hybrid_data::{render_modes, synthetic_hybrid_data, HybridDataShape};
+use memscope_stress_test::{js_syntax, report_data, seed};
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// Dataset rendered by every combination
+const DATA_SHAPE: HybridDataShape = HybridDataShape {
+    variables: 200,
+    threads: 8,
+    variables_per_task: 5,
+    samples: 30,
+};
+
+/// `FixedHybridTemplate::new` parameters, from minimal to oversized
+const TEMPLATE_PARAMS: [(usize, usize); 4] = [(1, 1), (5, 25), (16, 64), (64, 512)];
+
+/// One rendered combination and what validation found
+struct MatrixCell {
+    mode: &'static str,
+    params: (usize, usize),
+    variable_details: bool,
+    enhanced_insights: bool,
+    html_bytes: usize,
+    generation_time: Duration,
+    problems: Vec<String>,
+}
+
+impl MatrixCell {
+    fn file_stem(&self) -> String {
+        format!(
+            "{}_{}x{}_details-{}_insights-{}",
+            self.mode,
+            self.params.0,
+            self.params.1,
+            on_off(self.variable_details),
+            on_off(self.enhanced_insights)
+        )
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🧮 Render-Mode Matrix for FixedHybridTemplate Dashboards");
+    println!("========================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let output_dir = PathBuf::from("./Memoryanalysis/render_mode_matrix");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let data = synthetic_hybrid_data(DATA_SHAPE, &mut seed::rng(seed));
+    println!(
+        "   📦 Dataset: {} variables, {} threads, {} samples",
+        data.variable_registry.len(),
+        data.thread_task_mapping.len(),
+        DATA_SHAPE.samples
+    );
+
+    let mut cells = Vec::new();
+    for (mode, render_mode) in render_modes() {
+        for params in TEMPLATE_PARAMS {
+            for variable_details in [true, false] {
+                for enhanced_insights in [true, false] {
+                    let template = FixedHybridTemplate::new(params.0, params.1)
+                        .with_render_mode(render_mode())
+                        .with_variable_details(variable_details)
+                        .with_enhanced_insights(enhanced_insights);
+
+                    let started = Instant::now();
+                    let rendered = template.generate_hybrid_dashboard(&data);
+                    let generation_time = started.elapsed();
+
+                    let mut cell = MatrixCell {
+                        mode,
+                        params,
+                        variable_details,
+                        enhanced_insights,
+                        html_bytes: 0,
+                        generation_time,
+                        problems: Vec::new(),
+                    };
+                    match rendered {
+                        Ok(mut html) => {
+                            cell.html_bytes = html.len();
+                            cell.problems = validate_dashboard(&html, &data, variable_details);
+                            html.push_str(&seed::html_comment(seed));
+                            std::fs::write(
+                                output_dir.join(format!("{}.html", cell.file_stem())),
+                                html,
+                            )?;
+                        }
+                        Err(e) => cell.problems.push(format!("generation failed: {}", e)),
+                    }
+
+                    println!(
+                        "   {} {:<60} {:>9} bytes {:>8.2} ms",
+                        if cell.problems.is_empty() {
+                            "✅"
+                        } else {
+                            "❌"
+                        },
+                        cell.file_stem(),
+                        cell.html_bytes,
+                        cell.generation_time.as_secs_f64() * 1000.0
+                    );
+                    for problem in &cell.problems {
+                        println!("      • {}", problem);
+                    }
+                    cells.push(cell);
+                }
+            }
+        }
+    }
+
+    let report_path = output_dir.join("render_mode_matrix.md");
+    std::fs::write(&report_path, render_matrix_table(&cells, seed))?;
+    println!("\n📄 Matrix report: {}", report_path.display());
+
+    let failed = cells.iter().filter(|c| !c.problems.is_empty()).count();
+    seed::announce(seed);
+    if failed == 0 {
+        println!(
+            "   ✅ SUCCESS: all {} combinations rendered valid dashboards",
+            cells.len()
+        );
+        Ok(())
+    } else {
+        println!(
+            "   ❌ FAILED: {} of {} combinations produced invalid dashboards",
+            failed,
+            cells.len()
+        );
+        Err(format!("{} render combinations failed validation", failed).into())
+    }
+}
+
+/// Structural and data checks that must hold in every render mode
+fn validate_dashboard(
+    html: &str,
+    data: &HybridAnalysisData,
+    variable_details: bool,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    let doctype = "<!DOCTYPE html>";
+    let has_doctype = html
+        .trim_start()
+        .get(..doctype.len())
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(doctype));
+    if !has_doctype {
+        problems.push("missing <!DOCTYPE html>".to_string());
+    }
+    if !html.trim_end().ends_with("</html>") {
+        problems.push("document does not end with </html>".to_string());
+    }
+    let opened = html.matches("<script").count();
+    let closed = html.matches("</script>").count();
+    if opened != closed {
+        problems.push(format!("{} <script> tags but {} </script>", opened, closed));
+    }
+
+    problems.extend(
+        js_syntax::check_html(html)
+            .iter()
+            .map(|error| format!("JavaScript {}", error)),
+    );
+
+    let embedded = |key| report_data::embedded_array(html, report_data::DASHBOARD_DATA, key);
+    match embedded("variables") {
+        Ok(variables) => {
+            let input_names: HashSet<&str> = data
+                .variable_registry
+                .values()
+                .map(|v| v.name.as_str())
+                .collect();
+            let unknown = variables
+                .iter()
+                .filter(|v| !input_names.contains(v["name"].as_str().unwrap_or_default()))
+                .count();
+            if unknown > 0 {
+                problems.push(format!(
+                    "{} rendered variables are not in the input",
+                    unknown
+                ));
+            }
+            if variable_details && variables.is_empty() {
+                problems.push("variable details enabled but no variables rendered".to_string());
+            }
+        }
+        Err(e) => problems.push(e),
+    }
+    match embedded("threads") {
+        Ok(threads) => {
+            let rendered: HashSet<u64> = threads.iter().filter_map(|t| t["id"].as_u64()).collect();
+            let missing = data
+                .thread_task_mapping
+                .keys()
+                .filter(|&&tid| !rendered.contains(&(tid as u64)))
+                .count();
+            if missing > 0 {
+                problems.push(format!("{} mapped threads are not rendered", missing));
+            }
+        }
+        Err(e) => problems.push(e),
+    }
+    if let Err(e) = embedded("tasks") {
+        problems.push(e);
+    }
+
+    problems
+}
+
+/// Render size and generation time of every combination as a markdown table
+fn render_matrix_table(cells: &[MatrixCell], seed: u64) -> String {
+    let mut table = String::new();
+    let _ = writeln!(table, "# FixedHybridTemplate Render-Mode Matrix\n");
+    let _ = writeln!(
+        table,
+        "- Seed: {seed} (reproduce with `{} {seed}`)",
+        seed::SEED_FLAG
+    );
+    let _ = writeln!(
+        table,
+        "- Dataset: {} variables, {} threads, {} variables per task, {} samples\n",
+        DATA_SHAPE.variables, DATA_SHAPE.threads, DATA_SHAPE.variables_per_task, DATA_SHAPE.samples
+    );
+    let _ = writeln!(
+        table,
+        "| Mode | new() params | Variable details | Enhanced insights | HTML bytes | Generation (ms) | Result |"
+    );
+    let _ = writeln!(table, "|---|---|---|---|---:|---:|---|");
+    for cell in cells {
+        let _ = writeln!(
+            table,
+            "| {} | ({}, {}) | {} | {} | {} | {:.2} | {} |",
+            cell.mode,
+            cell.params.0,
+            cell.params.1,
+            on_off(cell.variable_details),
+            on_off(cell.enhanced_insights),
+            cell.html_bytes,
+            cell.generation_time.as_secs_f64() * 1000.0,
+            if cell.problems.is_empty() {
+                "ok".to_string()
+            } else {
+                cell.problems.join("; ")
+            }
+        );
+    }
+    table
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
@@ -0,0 +1,158 @@
+//! Access to the data embedded in generated HTML reports
+//!
+//! The binary dashboard assigns plain JSON to `window.analysisData`. The
+//! hybrid dashboard embeds its data as a JS object literal with unquoted
+//! keys whose values are JSON arrays:
+//!
+//! ```text
+//! window.DASHBOARD_DATA = {
+//!     variables: [{"name":"tcp_recv_buffer","size":8192,"thread":1,...}],
+//!     threads: [{"id":1,"memory":16697,"variables":10}],
+//!     tasks: [{"id":112,"memory":7813,"variables":1,"thread":1}],
+//! };
+//! ```
+
+use serde_json::Value;
+
+/// Global the hybrid dashboard assigns its data to
+pub const DASHBOARD_DATA: &str = "window.DASHBOARD_DATA";
+
+/// Global the binary dashboard assigns its data to
+pub const ANALYSIS_DATA: &str = "window.analysisData";
+
+/// Parse the JSON object first assigned to `global`
+pub fn embedded_object(html: &str, global: &str) -> Result<Value, String> {
+    let assignment = html
+        .find(&format!("{} =", global))
+        .ok_or_else(|| format!("{} not found in report", global))?;
+    let object = &html[assignment..];
+    let object_start = object
+        .find('{')
+        .ok_or_else(|| format!("{} is not an object", global))?;
+    let object = &object[object_start..];
+    let object_len = balanced_len(object, '{', '}')
+        .ok_or_else(|| format!("{} object is not terminated", global))?;
+
+    serde_json::from_str(&object[..object_len])
+        .map_err(|e| format!("Failed to parse {}: {}", global, e))
+}
+
+/// Parse the `key: [...]` array inside the object assigned to `global`
+pub fn embedded_array(html: &str, global: &str, key: &str) -> Result<Vec<Value>, String> {
+    let assignment = html
+        .find(&format!("{} =", global))
+        .ok_or_else(|| format!("{} not found in report", global))?;
+    let object = &html[assignment..];
+    let object = &object[..balanced_len(object, '{', '}')
+        .ok_or_else(|| format!("{} object is not terminated", global))?];
+
+    let key_start = find_key(object, key).ok_or_else(|| format!("{}.{} not found", global, key))?;
+    let array = &object[key_start..];
+    let array_start = array
+        .find('[')
+        .ok_or_else(|| format!("{}.{} is not an array", global, key))?;
+    let array = &array[array_start..];
+    let array_len = balanced_len(array, '[', ']')
+        .ok_or_else(|| format!("{}.{} array is not terminated", global, key))?;
+
+    serde_json::from_str(&array[..array_len])
+        .map_err(|e| format!("Failed to parse {}.{}: {}", global, key, e))
+}
+
+/// Position just past `key:` at the top level of an object literal
+fn find_key(object: &str, key: &str) -> Option<usize> {
+    let needle = format!("{}:", key);
+    let mut search_from = 0;
+    while let Some(offset) = object[search_from..].find(&needle) {
+        let pos = search_from + offset;
+        let preceded_by_ident = object[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '"');
+        if !preceded_by_ident {
+            return Some(pos + needle.len());
+        }
+        search_from = pos + needle.len();
+    }
+    None
+}
+
+/// Byte length of the bracketed region starting at the first `open`,
+/// skipping brackets inside string literals
+fn balanced_len(text: &str, open: char, close: char) -> Option<usize> {
+    let start = text.find(open)?;
+    let mut depth = 0usize;
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+
+    for (idx, c) in text[start..].char_indices() {
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                in_string = None;
+            }
+            continue;
+        }
+
+        match c {
+            '"' | '\'' | '`' => in_string = Some(c),
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(start + idx + c.len_utf8());
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Every flat array literal of numbers in `html`, e.g. chart series
+pub fn numeric_arrays(html: &str) -> Vec<Vec<f64>> {
+    let mut arrays = Vec::new();
+    for (open, _) in html.match_indices('[') {
+        let body = &html[open + 1..];
+        let Some(close) = body.find([']', '[']) else {
+            break;
+        };
+        if body.as_bytes()[close] == b'[' {
+            continue;
+        }
+        let values: Result<Vec<f64>, _> = body[..close]
+            .split(',')
+            .map(|v| v.trim().parse::<f64>())
+            .collect();
+        if let Ok(values) = values {
+            arrays.push(values);
+        }
+    }
+    arrays
+}
+
+/// Whether `html` contains a numeric array equal to `series` (to 0.1% or 0.05,
+/// allowing for values rendered with one decimal)
+///
+/// An all-zero series matches any zero-filled array of its length, so it is
+/// never reported as found; use [`is_trivial_series`] to tell the cases apart.
+pub fn contains_series(html: &str, series: &[f64]) -> bool {
+    if is_trivial_series(series) {
+        return false;
+    }
+    numeric_arrays(html).iter().any(|array| {
+        array.len() == series.len()
+            && array
+                .iter()
+                .zip(series)
+                .all(|(a, b)| (a - b).abs() <= (b.abs() * 1e-3).max(0.05))
+    })
+}
+
+/// Whether `series` carries no values that could identify it in a report
+pub fn is_trivial_series(series: &[f64]) -> bool {
+    series.iter().all(|value| *value == 0.0)
+}
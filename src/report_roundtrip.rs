@@ -0,0 +1,508 @@
+//! Typed round trip of the data embedded in generated reports
+//!
+//! The embedded payloads are deserialized into structs shaped like the report
+//! input (`AllocationInfo` for the binary dashboard, `VariableDetail` for the
+//! hybrid dashboard) and compared field by field, so a field the exporter
+//! drops or alters shows up as a precise mismatch. Allocations read back from
+//! an exported `.memscope` file are compared with the input the same way over
+//! the fields the binary format persists; extension fields it drops are
+//! reported separately.
+
+use memscope_rs::core::types::AllocationInfo;
+use memscope_rs::export::fixed_hybrid_template::VariableDetail;
+use serde::{Deserialize, Deserializer};
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use crate::report_data;
+
+/// An allocation as embedded in `window.analysisData.allocations`
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddedAllocation {
+    #[serde(deserialize_with = "address")]
+    pub ptr: usize,
+    pub size: usize,
+    pub var_name: Option<String>,
+    pub type_name: Option<String>,
+    pub scope_name: Option<String>,
+    pub thread_id: String,
+    pub timestamp_alloc: u64,
+    pub timestamp_dealloc: Option<u64>,
+    pub borrow_count: usize,
+    pub is_leaked: bool,
+    pub lifetime_ms: Option<u64>,
+    #[serde(default)]
+    pub ownership_history_available: bool,
+    pub borrow_info: Option<EmbeddedBorrowInfo>,
+    pub clone_info: Option<EmbeddedCloneInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddedBorrowInfo {
+    pub immutable_borrows: usize,
+    pub mutable_borrows: usize,
+    pub max_concurrent_borrows: usize,
+    pub last_borrow_timestamp: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddedCloneInfo {
+    pub clone_count: usize,
+    pub is_clone: bool,
+    #[serde(default, deserialize_with = "optional_address")]
+    pub original_ptr: Option<usize>,
+}
+
+/// A variable as embedded in `window.DASHBOARD_DATA.variables`
+#[derive(Debug, Clone, Deserialize)]
+pub struct EmbeddedVariable {
+    pub name: String,
+    pub size: u64,
+    pub thread: usize,
+    pub state: String,
+    pub allocs: u64,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawAddress {
+    Number(usize),
+    Text(String),
+}
+
+impl RawAddress {
+    fn resolve<E: serde::de::Error>(self) -> Result<usize, E> {
+        match self {
+            RawAddress::Number(value) => Ok(value),
+            RawAddress::Text(text) => {
+                let parsed = match text.strip_prefix("0x") {
+                    Some(hex) => usize::from_str_radix(hex, 16),
+                    None => text.parse(),
+                };
+                parsed.map_err(|e| E::custom(format!("invalid address '{}': {}", text, e)))
+            }
+        }
+    }
+}
+
+/// Addresses are embedded either as numbers or as `"0x..."` strings
+fn address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+    RawAddress::deserialize(deserializer)?.resolve()
+}
+
+fn optional_address<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<usize>, D::Error> {
+    Option::<RawAddress>::deserialize(deserializer)?
+        .map(RawAddress::resolve)
+        .transpose()
+}
+
+/// One field that differs between input and report
+#[derive(Debug, Clone)]
+pub struct FieldMismatch {
+    /// Which record, e.g. `0x1100 (user_data)`
+    pub record: String,
+    pub field: &'static str,
+    pub expected: String,
+    pub found: String,
+}
+
+impl fmt::Display for FieldMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}: expected {}, found {}",
+            self.record, self.field, self.expected, self.found
+        )
+    }
+}
+
+/// Deserialize the allocations embedded in a binary dashboard
+pub fn embedded_allocations(html: &str) -> Result<Vec<EmbeddedAllocation>, String> {
+    let data = report_data::embedded_object(html, report_data::ANALYSIS_DATA)?;
+    serde_json::from_value(data["allocations"].clone())
+        .map_err(|e| format!("Failed to deserialize embedded allocations: {}", e))
+}
+
+/// Deserialize the variables embedded in a hybrid dashboard
+pub fn embedded_variables(html: &str) -> Result<Vec<EmbeddedVariable>, String> {
+    report_data::embedded_array(html, report_data::DASHBOARD_DATA, "variables")?
+        .into_iter()
+        .map(|value| {
+            serde_json::from_value(value)
+                .map_err(|e| format!("Failed to deserialize embedded variable: {}", e))
+        })
+        .collect()
+}
+
+/// Collects mismatches for one record
+struct RecordDiff<'a> {
+    record: String,
+    mismatches: &'a mut Vec<FieldMismatch>,
+}
+
+impl RecordDiff<'_> {
+    fn field<T: PartialEq + fmt::Debug>(&mut self, field: &'static str, expected: T, found: T) {
+        if expected != found {
+            self.mismatches.push(FieldMismatch {
+                record: self.record.clone(),
+                field,
+                expected: format!("{:?}", expected),
+                found: format!("{:?}", found),
+            });
+        }
+    }
+}
+
+/// Compare every exported allocation with the embedded one at the same
+/// position
+///
+/// The report embeds the records in the order the binary file holds them, and
+/// a pointer can be reused once its allocation is freed, so records are paired
+/// by position after checking the count, as in [`diff_read_back`].
+pub fn diff_allocations(
+    expected: &[AllocationInfo],
+    embedded: &[EmbeddedAllocation],
+) -> Vec<FieldMismatch> {
+    let mut mismatches = Vec::new();
+    if embedded.len() != expected.len() {
+        mismatches.push(FieldMismatch {
+            record: "allocations".to_string(),
+            field: "len",
+            expected: expected.len().to_string(),
+            found: embedded.len().to_string(),
+        });
+        return mismatches;
+    }
+
+    for (index, (input, found)) in expected.iter().zip(embedded).enumerate() {
+        let mut diff = RecordDiff {
+            record: read_back_record(index, input),
+            mismatches: &mut mismatches,
+        };
+
+        diff.field("ptr", input.ptr, found.ptr);
+        diff.field("size", input.size, found.size);
+        diff.field("var_name", &input.var_name, &found.var_name);
+        diff.field("type_name", &input.type_name, &found.type_name);
+        diff.field("scope_name", &input.scope_name, &found.scope_name);
+        diff.field("thread_id", &input.thread_id, &found.thread_id);
+        diff.field(
+            "timestamp_alloc",
+            input.timestamp_alloc,
+            found.timestamp_alloc,
+        );
+        diff.field(
+            "timestamp_dealloc",
+            input.timestamp_dealloc,
+            found.timestamp_dealloc,
+        );
+        diff.field("borrow_count", input.borrow_count, found.borrow_count);
+        diff.field("is_leaked", input.is_leaked, found.is_leaked);
+        diff.field("lifetime_ms", input.lifetime_ms, found.lifetime_ms);
+        diff.field(
+            "ownership_history_available",
+            input.ownership_history_available,
+            found.ownership_history_available,
+        );
+
+        diff.field(
+            "borrow_info",
+            input.borrow_info.as_ref().map(|b| {
+                (
+                    b.immutable_borrows,
+                    b.mutable_borrows,
+                    b.max_concurrent_borrows,
+                    b.last_borrow_timestamp,
+                )
+            }),
+            found.borrow_info.as_ref().map(|b| {
+                (
+                    b.immutable_borrows,
+                    b.mutable_borrows,
+                    b.max_concurrent_borrows,
+                    b.last_borrow_timestamp,
+                )
+            }),
+        );
+        diff.field(
+            "clone_info",
+            input
+                .clone_info
+                .as_ref()
+                .map(|c| (c.clone_count, c.is_clone, c.original_ptr)),
+            found
+                .clone_info
+                .as_ref()
+                .map(|c| (c.clone_count, c.is_clone, c.original_ptr)),
+        );
+    }
+    mismatches
+}
+
+/// Compare every exported allocation with the one read back from the binary
+/// file at the same position, across the fields the binary format persists
+///
+/// The reader returns records in write order, so a count mismatch is reported
+/// on its own instead of pairing unrelated records. The extension fields
+/// (`smart_pointer_info` through `drop_chain_analysis`) are not written to the
+/// file and are checked separately by [`diff_lost_extensions`].
+pub fn diff_read_back(
+    expected: &[AllocationInfo],
+    read_back: &[AllocationInfo],
+) -> Vec<FieldMismatch> {
+    let mut mismatches = Vec::new();
+    if read_back.len() != expected.len() {
+        mismatches.push(FieldMismatch {
+            record: "allocations".to_string(),
+            field: "len",
+            expected: expected.len().to_string(),
+            found: read_back.len().to_string(),
+        });
+        return mismatches;
+    }
+
+    for (index, (input, found)) in expected.iter().zip(read_back).enumerate() {
+        let mut diff = RecordDiff {
+            record: read_back_record(index, input),
+            mismatches: &mut mismatches,
+        };
+
+        diff.field("ptr", input.ptr, found.ptr);
+        diff.field("size", input.size, found.size);
+        diff.field("var_name", &input.var_name, &found.var_name);
+        diff.field("type_name", &input.type_name, &found.type_name);
+        diff.field("scope_name", &input.scope_name, &found.scope_name);
+        diff.field(
+            "timestamp_alloc",
+            input.timestamp_alloc,
+            found.timestamp_alloc,
+        );
+        diff.field(
+            "timestamp_dealloc",
+            input.timestamp_dealloc,
+            found.timestamp_dealloc,
+        );
+        diff.field("thread_id", &input.thread_id, &found.thread_id);
+        diff.field("borrow_count", input.borrow_count, found.borrow_count);
+        diff.field("stack_trace", &input.stack_trace, &found.stack_trace);
+        diff.field("is_leaked", input.is_leaked, found.is_leaked);
+        diff.field("lifetime_ms", input.lifetime_ms, found.lifetime_ms);
+        diff.field("borrow_info", &input.borrow_info, &found.borrow_info);
+        diff.field("clone_info", &input.clone_info, &found.clone_info);
+        diff.field(
+            "ownership_history_available",
+            input.ownership_history_available,
+            found.ownership_history_available,
+        );
+    }
+    mismatches
+}
+
+/// Extension fields of the input that did not survive the binary round trip,
+/// paired by position as in [`diff_read_back`]
+pub fn diff_lost_extensions(
+    expected: &[AllocationInfo],
+    read_back: &[AllocationInfo],
+) -> Vec<FieldMismatch> {
+    let mut mismatches = Vec::new();
+    if read_back.len() != expected.len() {
+        return mismatches;
+    }
+
+    for (index, (input, found)) in expected.iter().zip(read_back).enumerate() {
+        let mut diff = RecordDiff {
+            record: read_back_record(index, input),
+            mismatches: &mut mismatches,
+        };
+
+        diff.field(
+            "smart_pointer_info",
+            &input.smart_pointer_info,
+            &found.smart_pointer_info,
+        );
+        diff.field("memory_layout", &input.memory_layout, &found.memory_layout);
+        diff.field("generic_info", &input.generic_info, &found.generic_info);
+        diff.field(
+            "dynamic_type_info",
+            &input.dynamic_type_info,
+            &found.dynamic_type_info,
+        );
+        diff.field("runtime_state", &input.runtime_state, &found.runtime_state);
+        diff.field(
+            "stack_allocation",
+            &input.stack_allocation,
+            &found.stack_allocation,
+        );
+        diff.field(
+            "temporary_object",
+            &input.temporary_object,
+            &found.temporary_object,
+        );
+        diff.field(
+            "fragmentation_analysis",
+            &input.fragmentation_analysis,
+            &found.fragmentation_analysis,
+        );
+        diff.field(
+            "generic_instantiation",
+            &input.generic_instantiation,
+            &found.generic_instantiation,
+        );
+        diff.field(
+            "type_relationships",
+            &input.type_relationships,
+            &found.type_relationships,
+        );
+        diff.field("type_usage", &input.type_usage, &found.type_usage);
+        diff.field(
+            "function_call_tracking",
+            &input.function_call_tracking,
+            &found.function_call_tracking,
+        );
+        diff.field(
+            "lifecycle_tracking",
+            &input.lifecycle_tracking,
+            &found.lifecycle_tracking,
+        );
+        diff.field(
+            "access_tracking",
+            &input.access_tracking,
+            &found.access_tracking,
+        );
+        diff.field(
+            "drop_chain_analysis",
+            &input.drop_chain_analysis,
+            &found.drop_chain_analysis,
+        );
+    }
+    mismatches
+}
+
+fn read_back_record(index: usize, input: &AllocationInfo) -> String {
+    format!(
+        "#{} {:#x} ({})",
+        index,
+        input.ptr,
+        input.var_name.as_deref().unwrap_or("?")
+    )
+}
+
+/// Compare every variable detail with its embedded copy
+///
+/// Names are not unique (a loop variable is tracked once per iteration), so
+/// records are paired within each name after sorting by thread and size.
+pub fn diff_variables<'a, I>(expected: I, embedded: &[EmbeddedVariable]) -> Vec<FieldMismatch>
+where
+    I: IntoIterator<Item = &'a VariableDetail>,
+{
+    let mut expected_by_name: BTreeMap<&str, Vec<&VariableDetail>> = BTreeMap::new();
+    let mut expected_count = 0;
+    for detail in expected {
+        expected_by_name
+            .entry(&detail.name)
+            .or_default()
+            .push(detail);
+        expected_count += 1;
+    }
+    let mut embedded_by_name: HashMap<&str, Vec<&EmbeddedVariable>> = HashMap::new();
+    for variable in embedded {
+        embedded_by_name
+            .entry(&variable.name)
+            .or_default()
+            .push(variable);
+    }
+
+    let mut mismatches = Vec::new();
+    for (name, mut details) in expected_by_name {
+        let mut found = embedded_by_name.remove(name).unwrap_or_default();
+        details.sort_by_key(|d| (d.thread_id, d.memory_usage));
+        found.sort_by_key(|v| (v.thread, v.size));
+
+        let mut diff = RecordDiff {
+            record: name.to_string(),
+            mismatches: &mut mismatches,
+        };
+        diff.field("count", details.len(), found.len());
+        for (detail, variable) in details.iter().zip(&found) {
+            diff.field("thread", detail.thread_id, variable.thread);
+            diff.field("size", detail.memory_usage, variable.size);
+            diff.field("allocs", detail.allocation_count, variable.allocs);
+            diff.field(
+                "state",
+                format!("{:?}", detail.lifecycle_stage),
+                variable.state.clone(),
+            );
+        }
+    }
+
+    for name in embedded_by_name.keys() {
+        mismatches.push(FieldMismatch {
+            record: name.to_string(),
+            field: "name",
+            expected: "no such variable".to_string(),
+            found: "embedded in report".to_string(),
+        });
+    }
+    if embedded.len() != expected_count {
+        mismatches.push(FieldMismatch {
+            record: "variables".to_string(),
+            field: "len",
+            expected: expected_count.to_string(),
+            found: embedded.len().to_string(),
+        });
+    }
+    mismatches
+}
+
+/// Print the outcome of a diff, one line per mismatched field
+pub fn report_mismatches(
+    label: &str,
+    records: usize,
+    mismatches: &[FieldMismatch],
+) -> Result<(), String> {
+    if mismatches.is_empty() {
+        println!(
+            "   ✅ Round trip: {} {} match the input field by field",
+            records, label
+        );
+        Ok(())
+    } else {
+        println!(
+            "   ❌ Round trip: {} field mismatch(es) in {} {}",
+            mismatches.len(),
+            records,
+            label
+        );
+        for mismatch in mismatches {
+            println!("      • {}", mismatch);
+        }
+        Err(format!("{} round trip: {}", label, mismatches[0]))
+    }
+}
+
+/// Print which extension fields the binary format dropped, one line per field;
+/// informational, since the format does not persist them
+pub fn report_lost_extensions(records: usize, lost: &[FieldMismatch]) {
+    if lost.is_empty() {
+        println!("   ✅ Extension fields: every populated field survived the binary file");
+        return;
+    }
+    let mut by_field: Vec<(&str, usize)> = Vec::new();
+    for mismatch in lost {
+        match by_field
+            .iter_mut()
+            .find(|(field, _)| *field == mismatch.field)
+        {
+            Some((_, count)) => *count += 1,
+            None => by_field.push((mismatch.field, 1)),
+        }
+    }
+    println!("   ⚠️  Extension fields not persisted by the binary format:");
+    for (field, count) in by_field {
+        println!(
+            "      • {}: lost in {} of {} records",
+            field, count, records
+        );
+    }
+}
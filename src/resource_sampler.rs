@@ -0,0 +1,185 @@
+//! Background resource sampler feeding hybrid dashboard time series
+//!
+//! CPU, I/O and per-thread CPU come from memscope's `PlatformResourceCollector`.
+//! Memory comes from the variable registry, grouped by memscope thread id, so
+//! the memory breakdown uses the same thread ids as the rest of the dashboard.
+//!
+//! The collector keys threads by OS thread id, the registry by the number in
+//! Rust's `ThreadId`. Threads that call [`register_current_thread`] have their
+//! CPU usage re-keyed to that number, so both breakdowns share one id space;
+//! CPU of unregistered threads (the sampler itself, runtime pools) is left out.
+
+use memscope_rs::export::fixed_hybrid_template::PerformanceTimeSeries;
+use memscope_rs::lockfree::PlatformResourceCollector;
+use memscope_rs::variable_registry::VariableRegistry;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle, ThreadId};
+use std::time::{Duration, Instant};
+
+/// OS thread id → `ThreadId` number of every registered thread
+static THREAD_IDS: Mutex<BTreeMap<u64, usize>> = Mutex::new(BTreeMap::new());
+
+/// One point of every series
+#[derive(Debug, Clone)]
+pub struct ResourceSample {
+    /// Milliseconds since the sampler started
+    pub elapsed_ms: u64,
+    pub cpu_usage: f64,
+    /// Bytes held by tracked variables
+    pub memory_usage: u64,
+    /// Disk read + write operations per second
+    pub io_operations: u64,
+    /// Network rx + tx bytes per second
+    pub network_bytes: u64,
+    /// CPU usage of registered threads keyed by memscope thread id
+    pub thread_cpu: HashMap<usize, f64>,
+    /// Tracked bytes keyed by memscope thread id
+    pub thread_memory: HashMap<usize, u64>,
+}
+
+/// Handle to a running sampler thread
+pub struct ResourceSampler {
+    active: Arc<AtomicBool>,
+    handle: JoinHandle<Vec<ResourceSample>>,
+}
+
+impl ResourceSampler {
+    /// Start sampling every `interval`; one sample is taken immediately
+    pub fn start(interval: Duration) -> Result<Self, String> {
+        let mut collector = PlatformResourceCollector::new()
+            .map_err(|e| format!("Failed to create resource collector: {}", e))?;
+
+        let active = Arc::new(AtomicBool::new(true));
+        let running = Arc::clone(&active);
+        let handle = thread::spawn(move || {
+            let started = Instant::now();
+            let mut samples = Vec::new();
+            loop {
+                // Read the flag first so a final sample is always taken after stop
+                let keep_running = running.load(Ordering::Relaxed);
+                if let Some(sample) = take_sample(&mut collector, started) {
+                    samples.push(sample);
+                }
+                if !keep_running {
+                    break;
+                }
+                thread::sleep(interval);
+            }
+            samples
+        });
+
+        Ok(Self { active, handle })
+    }
+
+    /// Stop the sampler and return every sample taken
+    pub fn stop(self) -> Result<Vec<ResourceSample>, String> {
+        self.active.store(false, Ordering::Relaxed);
+        self.handle
+            .join()
+            .map_err(|_| "resource sampler thread panicked".to_string())
+    }
+}
+
+/// Report the calling thread's CPU usage under the id memscope's variable
+/// registry gives it; call once at the start of every worker thread
+pub fn register_current_thread() {
+    if let (Some(os_tid), Some(number)) = (os_thread_id(), thread_number(thread::current().id())) {
+        THREAD_IDS.lock().unwrap().insert(os_tid, number);
+    }
+}
+
+/// Kernel thread id of the calling thread, from `/proc/thread-self`
+fn os_thread_id() -> Option<u64> {
+    std::fs::read_link("/proc/thread-self")
+        .ok()?
+        .file_name()?
+        .to_str()?
+        .parse()
+        .ok()
+}
+
+/// The number in `ThreadId(N)`, which memscope uses as its thread id
+fn thread_number(id: ThreadId) -> Option<usize> {
+    format!("{id:?}")
+        .strip_prefix("ThreadId(")?
+        .strip_suffix(')')?
+        .parse()
+        .ok()
+}
+
+fn take_sample(
+    collector: &mut PlatformResourceCollector,
+    started: Instant,
+) -> Option<ResourceSample> {
+    let metrics = collector.collect_metrics().ok()?;
+
+    let mut thread_memory: HashMap<usize, u64> = HashMap::new();
+    for variable in VariableRegistry::get_all_variables().values() {
+        *thread_memory.entry(variable.thread_id).or_default() += variable.memory_usage;
+    }
+
+    let thread_ids = THREAD_IDS.lock().unwrap().clone();
+    let io = &metrics.io_metrics;
+    Some(ResourceSample {
+        elapsed_ms: started.elapsed().as_millis() as u64,
+        cpu_usage: metrics.cpu_metrics.overall_usage_percent as f64,
+        memory_usage: thread_memory.values().sum(),
+        io_operations: io.disk_read_ops_per_sec + io.disk_write_ops_per_sec,
+        network_bytes: io.network_rx_bytes_per_sec + io.network_tx_bytes_per_sec,
+        thread_cpu: metrics
+            .thread_metrics
+            .iter()
+            .filter_map(|(tid, thread)| {
+                let number = *thread_ids.get(tid)?;
+                Some((number, thread.cpu_usage_percent as f64))
+            })
+            .collect(),
+        thread_memory,
+    })
+}
+
+/// Series for the hybrid dashboard, one point per sample
+///
+/// Threads missing from a sample get a zero point, so every per-thread series
+/// has as many points as there are samples.
+pub fn performance_time_series(samples: &[ResourceSample]) -> PerformanceTimeSeries {
+    let cpu_threads: BTreeSet<usize> = samples
+        .iter()
+        .flat_map(|s| s.thread_cpu.keys().copied())
+        .collect();
+    let memory_threads: BTreeSet<usize> = samples
+        .iter()
+        .flat_map(|s| s.thread_memory.keys().copied())
+        .collect();
+
+    PerformanceTimeSeries {
+        cpu_usage: samples.iter().map(|s| s.cpu_usage).collect(),
+        memory_usage: samples.iter().map(|s| s.memory_usage).collect(),
+        io_operations: samples.iter().map(|s| s.io_operations).collect(),
+        network_bytes: samples.iter().map(|s| s.network_bytes).collect(),
+        timestamps: samples.iter().map(|s| s.elapsed_ms).collect(),
+        thread_cpu_breakdown: cpu_threads
+            .into_iter()
+            .map(|tid| {
+                let series = samples
+                    .iter()
+                    .map(|s| s.thread_cpu.get(&tid).copied().unwrap_or_default())
+                    .collect();
+                (tid, series)
+            })
+            .collect(),
+        thread_memory_breakdown: memory_threads
+            .into_iter()
+            .map(|tid| {
+                let series = samples
+                    .iter()
+                    .map(|s| s.thread_memory.get(&tid).copied().unwrap_or_default())
+                    .collect();
+                (tid, series)
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,244 @@
+//! Declarative allocation scenarios loaded from TOML or JSON
+//!
+//! A scenario lists allocations by the `AllocationInfo` fields a test case
+//! cares about; everything else gets the defaults `binary_html` always used:
+//!
+//! ```toml
+//! name = "Cache under contention"
+//! covers = ["Arc<Mutex<..>> shared across workers"]
+//! base_ptr = 0x1100      # optional, pointers are assigned from here
+//! ptr_step = 0x100       # optional
+//!
+//! [[allocations]]
+//! var_name = "cache"
+//! type_name = "Arc<Mutex<HashMap<String, Entry>>>"
+//! size = 2048
+//! scope_name = "cache_module"   # default "main"
+//! thread_id = "worker_1"        # default "main"
+//! lifetime_ms = 300
+//! deallocated = true            # freed lifetime_ms after timestamp_alloc (ns)
+//! borrow_info = { immutable_borrows = 4, mutable_borrows = 1, max_concurrent_borrows = 2 }
+//! clone_info = { clone_count = 3, is_clone = true, original = "shared_cache" }
+//! ```
+//!
+//! `clone_info.original` names another allocation of the scenario by
+//! `var_name` and is resolved to its pointer. JSON files use the same field
+//! names. Unknown fields are rejected so typos don't silently fall back to
+//! defaults.
+
+use crate::allocation::{self, PLACEHOLDER_TIMESTAMP};
+use memscope_rs::core::types::{AllocationInfo, BorrowInfo, CloneInfo};
+use serde::Deserialize;
+use std::path::Path;
+
+/// The scenario behind `binary_html`'s comprehensive demo
+pub const COMPREHENSIVE_DEMO: &str = include_str!("../scenarios/comprehensive_demo.toml");
+
+const DEFAULT_BASE_PTR: usize = 0x1100;
+const DEFAULT_PTR_STEP: usize = 0x100;
+
+/// A named set of allocations
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scenario {
+    pub name: String,
+    /// What the scenario exercises, one line per item
+    #[serde(default)]
+    pub covers: Vec<String>,
+    #[serde(default = "default_base_ptr")]
+    pub base_ptr: usize,
+    #[serde(default = "default_ptr_step")]
+    pub ptr_step: usize,
+    pub allocations: Vec<ScenarioAllocation>,
+}
+
+/// One allocation of a scenario
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioAllocation {
+    pub var_name: String,
+    pub type_name: String,
+    pub size: usize,
+    /// Explicit pointer; otherwise `base_ptr + index * ptr_step`
+    pub ptr: Option<usize>,
+    #[serde(default = "default_label")]
+    pub scope_name: String,
+    #[serde(default = "default_label")]
+    pub thread_id: String,
+    #[serde(default = "default_timestamp_alloc")]
+    pub timestamp_alloc: u64,
+    pub lifetime_ms: Option<u64>,
+    #[serde(default)]
+    pub deallocated: bool,
+    #[serde(default)]
+    pub is_leaked: bool,
+    #[serde(default)]
+    pub ownership_history_available: bool,
+    /// Frames outermost last; defaults to `scope::var`, `main::run`,
+    /// `std::rt::lang_start`
+    pub stack_trace: Option<Vec<String>>,
+    pub borrow_info: Option<ScenarioBorrows>,
+    pub clone_info: Option<ScenarioClones>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioBorrows {
+    #[serde(default)]
+    pub immutable_borrows: usize,
+    #[serde(default)]
+    pub mutable_borrows: usize,
+    #[serde(default)]
+    pub max_concurrent_borrows: usize,
+    pub last_borrow_timestamp: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScenarioClones {
+    #[serde(default)]
+    pub clone_count: usize,
+    #[serde(default)]
+    pub is_clone: bool,
+    /// `var_name` of the allocation this one was cloned from
+    pub original: Option<String>,
+}
+
+fn default_base_ptr() -> usize {
+    DEFAULT_BASE_PTR
+}
+
+fn default_ptr_step() -> usize {
+    DEFAULT_PTR_STEP
+}
+
+fn default_label() -> String {
+    "main".to_string()
+}
+
+fn default_timestamp_alloc() -> u64 {
+    PLACEHOLDER_TIMESTAMP
+}
+
+/// Read a scenario, choosing the format from the `.toml` / `.json` extension
+pub fn load(path: &Path) -> Result<Scenario, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("cannot read scenario {}: {e}", path.display()))?;
+    let scenario = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => from_toml(&text),
+        Some("json") => from_json(&text),
+        _ => Err("expected a .toml or .json extension".to_string()),
+    };
+    scenario.map_err(|e| format!("scenario {}: {e}", path.display()))
+}
+
+pub fn from_toml(text: &str) -> Result<Scenario, String> {
+    toml::from_str(text).map_err(|e| e.to_string())
+}
+
+pub fn from_json(text: &str) -> Result<Scenario, String> {
+    serde_json::from_str(text).map_err(|e| e.to_string())
+}
+
+impl Scenario {
+    /// Build the allocations, resolving pointers and clone origins
+    pub fn allocations(&self) -> Result<Vec<AllocationInfo>, String> {
+        let ptrs = self
+            .allocations
+            .iter()
+            .enumerate()
+            .map(|(idx, spec)| match spec.ptr {
+                Some(ptr) => Ok(ptr),
+                None => idx
+                    .checked_mul(self.ptr_step)
+                    .and_then(|offset| self.base_ptr.checked_add(offset))
+                    .ok_or_else(|| format!("pointer of '{}' overflows", spec.var_name)),
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        self.allocations
+            .iter()
+            .zip(&ptrs)
+            .map(|(spec, &ptr)| self.allocation(spec, ptr, &ptrs))
+            .collect()
+    }
+
+    fn allocation(
+        &self,
+        spec: &ScenarioAllocation,
+        ptr: usize,
+        ptrs: &[usize],
+    ) -> Result<AllocationInfo, String> {
+        let mut info = allocation::allocation(
+            ptr,
+            spec.size,
+            &spec.var_name,
+            &spec.type_name,
+            &spec.scope_name,
+            &spec.thread_id,
+        );
+        info.timestamp_alloc = spec.timestamp_alloc;
+        info.lifetime_ms = spec.lifetime_ms;
+        info.is_leaked = spec.is_leaked;
+        info.ownership_history_available = spec.ownership_history_available;
+        info.stack_trace = Some(spec.stack_trace.clone().unwrap_or_else(|| {
+            vec![
+                format!("{}::{}", spec.scope_name, spec.var_name),
+                "main::run".to_string(),
+                "std::rt::lang_start".to_string(),
+            ]
+        }));
+
+        if spec.deallocated {
+            let lifetime = spec.lifetime_ms.ok_or_else(|| {
+                format!("'{}' is deallocated but has no lifetime_ms", spec.var_name)
+            })?;
+            if spec.is_leaked {
+                return Err(format!(
+                    "'{}' is both deallocated and leaked",
+                    spec.var_name
+                ));
+            }
+            info.timestamp_dealloc = Some(allocation::dealloc_timestamp(
+                spec.timestamp_alloc,
+                lifetime,
+            ));
+        }
+
+        if let Some(borrows) = &spec.borrow_info {
+            info.borrow_count = borrows.immutable_borrows + borrows.mutable_borrows;
+            info.borrow_info = Some(BorrowInfo {
+                immutable_borrows: borrows.immutable_borrows,
+                mutable_borrows: borrows.mutable_borrows,
+                max_concurrent_borrows: borrows.max_concurrent_borrows,
+                last_borrow_timestamp: borrows.last_borrow_timestamp,
+            });
+        }
+
+        if let Some(clones) = &spec.clone_info {
+            let original_ptr = match &clones.original {
+                Some(original) => {
+                    let idx = self
+                        .allocations
+                        .iter()
+                        .position(|other| other.var_name == *original)
+                        .ok_or_else(|| {
+                            format!(
+                                "'{}' is cloned from '{original}', which is not in the scenario",
+                                spec.var_name
+                            )
+                        })?;
+                    Some(ptrs[idx])
+                }
+                None => None,
+            };
+            info.clone_info = Some(CloneInfo {
+                clone_count: clones.clone_count,
+                is_clone: clones.is_clone,
+                original_ptr,
+            });
+        }
+
+        Ok(info)
+    }
+}
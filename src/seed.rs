@@ -0,0 +1,81 @@
+//! Seeded randomness so any scenario run can be reproduced bit-for-bit
+//!
+//! Every scenario takes `--seed <n>` (decimal or `0x` hex). Without it a fresh
+//! seed is drawn and printed, so a failing run can always be replayed.
+//! Streams come from `ChaCha8Rng`, whose output for a given seed is fixed
+//! across releases, unlike `StdRng`.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+use std::path::Path;
+
+use crate::cli;
+
+/// Command-line flag carrying the seed
+pub const SEED_FLAG: &str = "--seed";
+
+/// File written next to generated reports recording the seed that produced them
+pub const SEED_FILE: &str = "seed.txt";
+
+/// Seed from `--seed`, or a freshly drawn one when the flag is absent
+pub fn seed_from_args() -> Result<u64, String> {
+    match cli::flag_value(SEED_FLAG) {
+        Some(value) => parse_seed(&value),
+        None => Ok(rand::random()),
+    }
+}
+
+/// Parse a decimal or `0x`-prefixed hexadecimal seed
+pub fn parse_seed(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let parsed = match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    };
+    parsed.map_err(|e| format!("Invalid {} value '{}': {}", SEED_FLAG, value, e))
+}
+
+/// Print the seed in the banner of a scenario
+pub fn announce(seed: u64) {
+    println!(
+        "   🎲 Seed: {} (reproduce with {} {})",
+        seed, SEED_FLAG, seed
+    );
+}
+
+/// Write the seed into `output_dir` alongside the reports it produced
+pub fn record(output_dir: &Path, seed: u64) -> std::io::Result<()> {
+    std::fs::write(
+        output_dir.join(SEED_FILE),
+        format!("{}\nreproduce with: {} {}\n", seed, SEED_FLAG, seed),
+    )
+}
+
+/// HTML comment recording the seed, appended to generated dashboards
+pub fn html_comment(seed: u64) -> String {
+    format!("\n<!-- memscope-stress-test seed: {} -->\n", seed)
+}
+
+/// Main random stream of a scenario
+pub fn rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+/// Independent random stream for worker `stream`, stable regardless of how
+/// threads are scheduled
+pub fn stream_rng(seed: u64, stream: usize) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(splitmix64(
+        seed ^ splitmix64((stream as u64).wrapping_add(1)),
+    ))
+}
+
+/// SplitMix64 finaliser, used to decorrelate per-stream seeds
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
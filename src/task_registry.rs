@@ -0,0 +1,207 @@
+//! Registry of logical tasks and the tracked variables each one created
+//!
+//! Scenarios start a task on whichever thread runs it and attribute every
+//! `track_var!` call to that task. After the run the registry is joined with
+//! memscope's `VariableRegistry` (keyed by heap address) to recover which
+//! memscope thread each task ran on.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Identifier handed out by [`TaskRegistry::start_task`], starting at 1
+pub type TaskId = usize;
+
+/// A variable created by a task
+#[derive(Debug, Clone)]
+pub struct TaskVariable {
+    pub name: String,
+    pub addr: usize,
+    pub size: usize,
+    /// Set once the variable's [`DropGuard`] has been dropped
+    pub dropped: bool,
+}
+
+/// Allocation totals attributed to a task
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskTotals {
+    pub allocation_count: u64,
+    pub memory_usage: u64,
+}
+
+/// A logical unit of work and everything it tracked
+#[derive(Debug, Clone)]
+pub struct TaskRecord {
+    pub id: TaskId,
+    pub name: String,
+    pub variables: Vec<TaskVariable>,
+}
+
+/// Thread-safe collection of task records
+#[derive(Debug)]
+pub struct TaskRegistry {
+    next_id: AtomicUsize,
+    tasks: Mutex<Vec<TaskRecord>>,
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskRegistry {
+    pub fn new() -> Self {
+        Self {
+            next_id: AtomicUsize::new(1),
+            tasks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Register a task; its thread is resolved later from its variables
+    pub fn start_task(&self, name: &str) -> TaskId {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.tasks.lock().unwrap().push(TaskRecord {
+            id,
+            name: name.to_string(),
+            variables: Vec::new(),
+        });
+        id
+    }
+
+    /// Attribute a tracked variable to `task`
+    pub fn record_variable(&self, task: TaskId, name: &str, addr: usize, size: usize) {
+        let mut tasks = self.tasks.lock().unwrap();
+        if let Some(record) = tasks.iter_mut().find(|t| t.id == task) {
+            record.variables.push(TaskVariable {
+                name: name.to_string(),
+                addr,
+                size,
+                dropped: false,
+            });
+        }
+    }
+
+    /// Guard that marks the variable at `addr` dropped when it goes out of scope
+    ///
+    /// Declare it right after the variable so both leave scope together.
+    pub fn drop_guard(self: &Arc<Self>, task: TaskId, addr: usize) -> DropGuard {
+        DropGuard {
+            registry: Arc::clone(self),
+            task,
+            addr,
+        }
+    }
+
+    fn record_drop(&self, task: TaskId, addr: usize) {
+        let mut tasks = self.tasks.lock().unwrap();
+        if let Some(variable) = tasks
+            .iter_mut()
+            .find(|t| t.id == task)
+            .and_then(|t| t.variables.iter_mut().rev().find(|v| v.addr == addr))
+        {
+            variable.dropped = true;
+        }
+    }
+
+    /// Snapshot of every task, ordered by id
+    pub fn tasks(&self) -> Vec<TaskRecord> {
+        let mut tasks = self.tasks.lock().unwrap().clone();
+        tasks.sort_by_key(|t| t.id);
+        tasks
+    }
+
+    /// Task that most recently recorded a variable at `addr`
+    ///
+    /// Heap addresses can be reused once a variable is dropped, so the latest
+    /// task wins, matching how an address-keyed registry overwrites entries.
+    pub fn task_for_address(&self, addr: usize) -> Option<TaskId> {
+        let tasks = self.tasks.lock().unwrap();
+        tasks
+            .iter()
+            .filter(|t| t.variables.iter().any(|v| v.addr == addr))
+            .max_by_key(|t| t.id)
+            .map(|t| t.id)
+    }
+
+    /// Latest variable recorded at `addr`, by any task
+    pub fn variable_at(&self, addr: usize) -> Option<TaskVariable> {
+        let task = self.task_for_address(addr)?;
+        let tasks = self.tasks.lock().unwrap();
+        tasks
+            .iter()
+            .find(|t| t.id == task)
+            .and_then(|t| t.variables.iter().rev().find(|v| v.addr == addr))
+            .cloned()
+    }
+
+    /// Task that recorded a variable named `name`, if exactly one did
+    pub fn task_for_name(&self, name: &str) -> Option<TaskId> {
+        let tasks = self.tasks.lock().unwrap();
+        let mut owners = tasks
+            .iter()
+            .filter(|t| t.variables.iter().any(|v| v.name == name));
+        match (owners.next(), owners.next()) {
+            (Some(task), None) => Some(task.id),
+            _ => None,
+        }
+    }
+
+    /// Per-task totals over the variables each task still owns
+    ///
+    /// A variable whose address was later reused by another task counts
+    /// towards that later task only, as in [`Self::task_for_address`].
+    pub fn totals(&self) -> HashMap<TaskId, TaskTotals> {
+        let mut totals: HashMap<TaskId, TaskTotals> = HashMap::new();
+        for task in self.tasks() {
+            for variable in &task.variables {
+                if self.task_for_address(variable.addr) != Some(task.id) {
+                    continue;
+                }
+                let entry = totals.entry(task.id).or_default();
+                entry.allocation_count += 1;
+                entry.memory_usage += variable.size as u64;
+            }
+        }
+        totals
+    }
+
+    /// Build a thread → tasks mapping, resolving the thread of each task's
+    /// variables through `thread_of` (typically a lookup into memscope's
+    /// variable registry)
+    pub fn thread_task_mapping<F>(&self, thread_of: F) -> HashMap<usize, Vec<TaskId>>
+    where
+        F: Fn(&TaskVariable) -> Option<usize>,
+    {
+        let mut mapping: HashMap<usize, BTreeSet<TaskId>> = HashMap::new();
+        for task in self.tasks() {
+            for variable in &task.variables {
+                if self.task_for_address(variable.addr) != Some(task.id) {
+                    continue;
+                }
+                if let Some(thread_id) = thread_of(variable) {
+                    mapping.entry(thread_id).or_default().insert(task.id);
+                }
+            }
+        }
+
+        mapping
+            .into_iter()
+            .map(|(thread_id, tasks)| (thread_id, tasks.into_iter().collect()))
+            .collect()
+    }
+}
+
+/// Marks a task variable dropped when it goes out of scope
+#[derive(Debug)]
+pub struct DropGuard {
+    registry: Arc<TaskRegistry>,
+    task: TaskId,
+    addr: usize,
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.registry.record_drop(self.task, self.addr);
+    }
+}
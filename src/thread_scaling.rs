@@ -0,0 +1,424 @@
+//! Thread-Count Scaling Sweep for the Lockfree Tracker
+//!
+//! This scenario runs the lockfree worker at increasing thread counts, from a
+//! single thread up to several hundred (well past the number of cores), and
+//! records for every point:
+//! 1. Tracked allocations per second across all workers
+//! 2. Time spent in `LockfreeAggregator::aggregate_all_threads`
+//! 3. Time spent writing the JSON report
+//!
+//! The results are written as a markdown scaling table and an SVG chart so
+//! contention cliffs in `track_allocation_lockfree` are easy to spot.
+
+use memscope_rs::lockfree::aggregator::LockfreeAggregator;
+use memscope_rs::lockfree::tracker::{
+    finalize_thread_tracker, init_thread_tracker, track_allocation_lockfree, SamplingConfig,
+};
+use memscope_stress_test::seed;
+use rand::Rng;
+
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Allocations performed by every worker at each sweep point
+const ALLOCATIONS_PER_THREAD: usize = 200;
+
+/// Largest thread count in the sweep
+const MAX_THREADS: usize = 512;
+
+/// Allocation sizes drawn by the workers: 1KB, 4KB, 16KB, 8KB
+const ALLOC_SIZE_CLASSES: [usize; 4] = [1024, 4096, 16384, 8192];
+
+/// Per-thread throughput drop (relative to the previous point) reported as a cliff
+const CLIFF_THRESHOLD: f64 = 0.5;
+
+/// Measurements for a single thread count
+struct ScalingPoint {
+    threads: usize,
+    tracked_allocations: usize,
+    tracking_duration: Duration,
+    aggregation_duration: Duration,
+    report_duration: Duration,
+}
+
+impl ScalingPoint {
+    fn allocations_per_second(&self) -> f64 {
+        self.tracked_allocations as f64 / self.tracking_duration.as_secs_f64().max(1e-9)
+    }
+
+    fn per_thread_allocations_per_second(&self) -> f64 {
+        self.allocations_per_second() / self.threads as f64
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("📈 Thread-Count Scaling Sweep for track_allocation_lockfree");
+    println!("==========================================================");
+    let seed = seed::seed_from_args()?;
+    seed::announce(seed);
+
+    let cores = thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
+    let thread_counts = sweep_thread_counts(cores);
+
+    println!("   🧵 CPU cores detected: {}", cores);
+    println!("   📊 Sweep points: {:?}", thread_counts);
+    println!("   🔄 Allocations per thread: {}\n", ALLOCATIONS_PER_THREAD);
+
+    let output_dir = PathBuf::from("./Memoryanalysis/thread_scaling");
+    if output_dir.exists() {
+        std::fs::remove_dir_all(&output_dir)?;
+    }
+    std::fs::create_dir_all(&output_dir)?;
+    seed::record(&output_dir, seed)?;
+
+    let mut points = Vec::with_capacity(thread_counts.len());
+    for &threads in &thread_counts {
+        let point = run_sweep_point(
+            threads,
+            seed,
+            &output_dir.join(format!("threads_{threads}")),
+        )?;
+        println!(
+            "   Threads {:>4}: {:>12.0} allocs/s, aggregation {:>8.2} ms, report {:>8.2} ms",
+            point.threads,
+            point.allocations_per_second(),
+            point.aggregation_duration.as_secs_f64() * 1000.0,
+            point.report_duration.as_secs_f64() * 1000.0
+        );
+        points.push(point);
+    }
+
+    let cliffs = detect_contention_cliffs(&points);
+    println!("\n🔍 Contention Analysis:");
+    if cliffs.is_empty() {
+        println!("   ✅ No contention cliffs detected");
+    } else {
+        for (before, after) in &cliffs {
+            println!(
+                "   ⚠️  Cliff between {} and {} threads: per-thread throughput {:.0} → {:.0} allocs/s",
+                before.threads,
+                after.threads,
+                before.per_thread_allocations_per_second(),
+                after.per_thread_allocations_per_second()
+            );
+        }
+    }
+
+    let table_path = output_dir.join("thread_scaling.md");
+    std::fs::write(&table_path, render_scaling_table(&points, cores, seed))?;
+
+    let chart_path = output_dir.join("thread_scaling.svg");
+    std::fs::write(&chart_path, render_scaling_chart(&points, cores, seed))?;
+
+    println!("\n📄 Reports Generated:");
+    println!("   📋 Table: {}", table_path.display());
+    println!("   📈 Chart: {}", chart_path.display());
+
+    Ok(())
+}
+
+/// Build the list of thread counts: powers of two up to `MAX_THREADS`, plus the
+/// core count and a few multiples of it so the sweep always crosses it
+fn sweep_thread_counts(cores: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |&n| Some(n * 2))
+        .take_while(|&n| n <= MAX_THREADS)
+        .collect();
+    counts.extend(
+        [cores, cores * 2, cores * 4]
+            .into_iter()
+            .filter(|&n| n <= MAX_THREADS),
+    );
+    counts.sort_unstable();
+    counts.dedup();
+    counts
+}
+
+/// Run one sweep point with `threads` workers writing into `point_dir`
+fn run_sweep_point(
+    threads: usize,
+    seed: u64,
+    point_dir: &Path,
+) -> Result<ScalingPoint, Box<dyn std::error::Error>> {
+    std::fs::create_dir_all(point_dir)?;
+
+    let tracked_allocations = Arc::new(AtomicUsize::new(0));
+    // Workers plus the coordinating thread start the tracking phase together
+    let start_barrier = Arc::new(Barrier::new(threads + 1));
+
+    let handles: Vec<_> = (0..threads)
+        .map(|thread_idx| {
+            let point_dir = point_dir.to_path_buf();
+            let tracked_allocations = Arc::clone(&tracked_allocations);
+            let start_barrier = Arc::clone(&start_barrier);
+
+            thread::spawn(move || -> Result<(), String> {
+                run_sweep_worker(
+                    thread_idx,
+                    seed,
+                    &point_dir,
+                    &tracked_allocations,
+                    &start_barrier,
+                )
+            })
+        })
+        .collect();
+
+    start_barrier.wait();
+    let tracking_start = Instant::now();
+
+    let mut failures = Vec::new();
+    for (idx, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => failures.push(format!("thread {idx} failed: {e}")),
+            Err(_) => failures.push(format!("thread {idx} panicked")),
+        }
+    }
+    let tracking_duration = tracking_start.elapsed();
+
+    if !failures.is_empty() {
+        return Err(format!(
+            "{} of {} workers failed: {}",
+            failures.len(),
+            threads,
+            failures.join("; ")
+        )
+        .into());
+    }
+
+    let aggregator = LockfreeAggregator::new(point_dir.to_path_buf());
+
+    let aggregation_start = Instant::now();
+    let analysis = aggregator.aggregate_all_threads()?;
+    let aggregation_duration = aggregation_start.elapsed();
+
+    let json_path = point_dir.join("scaling_analysis.json");
+    let report_start = Instant::now();
+    aggregator.export_analysis(&analysis, &json_path)?;
+    let report_duration = report_start.elapsed();
+
+    Ok(ScalingPoint {
+        threads,
+        tracked_allocations: tracked_allocations.load(Ordering::Relaxed),
+        tracking_duration,
+        aggregation_duration,
+        report_duration,
+    })
+}
+
+/// Lockfree worker used at every sweep point
+fn run_sweep_worker(
+    thread_idx: usize,
+    seed: u64,
+    output_dir: &Path,
+    tracked_allocations: &AtomicUsize,
+    start_barrier: &Barrier,
+) -> Result<(), String> {
+    let init_result = init_thread_tracker(output_dir, Some(SamplingConfig::demo()));
+
+    // Always reach the barrier so a failed init cannot stall the other workers
+    start_barrier.wait();
+    init_result.map_err(|e| format!("Failed to init tracker: {}", e))?;
+
+    let mut rng = seed::stream_rng(seed, thread_idx);
+    for i in 0..ALLOCATIONS_PER_THREAD {
+        let alloc_size = ALLOC_SIZE_CLASSES[rng.random_range(0..ALLOC_SIZE_CLASSES.len())];
+        let data = vec![0u8; alloc_size];
+        let call_stack = vec![0x1000 + thread_idx, 0x2000 + i];
+
+        track_allocation_lockfree(data.as_ptr() as usize, alloc_size, &call_stack)
+            .map_err(|e| format!("Failed to track allocation: {}", e))?;
+    }
+
+    finalize_thread_tracker().map_err(|e| format!("Failed to finalize tracker: {}", e))?;
+    tracked_allocations.fetch_add(ALLOCATIONS_PER_THREAD, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Pairs of consecutive points where per-thread throughput drops by more than
+/// `CLIFF_THRESHOLD`
+fn detect_contention_cliffs(points: &[ScalingPoint]) -> Vec<(&ScalingPoint, &ScalingPoint)> {
+    points
+        .windows(2)
+        .filter(|pair| {
+            let before = pair[0].per_thread_allocations_per_second();
+            let after = pair[1].per_thread_allocations_per_second();
+            after < before * (1.0 - CLIFF_THRESHOLD)
+        })
+        .map(|pair| (&pair[0], &pair[1]))
+        .collect()
+}
+
+/// Render the sweep results as a markdown table
+fn render_scaling_table(points: &[ScalingPoint], cores: usize, seed: u64) -> String {
+    let mut table = String::new();
+    let _ = writeln!(table, "# Thread-Count Scaling Sweep\n");
+    let _ = writeln!(
+        table,
+        "- Seed: {seed} (reproduce with `{} {seed}`)",
+        seed::SEED_FLAG
+    );
+    let _ = writeln!(table, "- CPU cores: {cores}");
+    let _ = writeln!(
+        table,
+        "- Allocations per thread: {ALLOCATIONS_PER_THREAD}\n"
+    );
+    let _ = writeln!(
+        table,
+        "| Threads | Tracked allocations | Tracking (ms) | Allocs/s | Allocs/s per thread | Aggregation (ms) | Report (ms) |"
+    );
+    let _ = writeln!(table, "|---:|---:|---:|---:|---:|---:|---:|");
+    for point in points {
+        let _ = writeln!(
+            table,
+            "| {} | {} | {:.2} | {:.0} | {:.0} | {:.2} | {:.2} |",
+            point.threads,
+            point.tracked_allocations,
+            point.tracking_duration.as_secs_f64() * 1000.0,
+            point.allocations_per_second(),
+            point.per_thread_allocations_per_second(),
+            point.aggregation_duration.as_secs_f64() * 1000.0,
+            point.report_duration.as_secs_f64() * 1000.0
+        );
+    }
+    table
+}
+
+/// Render throughput and aggregation/report times against thread count as an
+/// SVG line chart with a log2 thread axis
+fn render_scaling_chart(points: &[ScalingPoint], cores: usize, seed: u64) -> String {
+    const WIDTH: f64 = 900.0;
+    const HEIGHT: f64 = 360.0;
+    const MARGIN: f64 = 60.0;
+
+    let plot_width = WIDTH - 2.0 * MARGIN;
+    let plot_height = HEIGHT - 2.0 * MARGIN;
+    let max_log = (MAX_THREADS as f64).log2();
+    let x_of = |threads: usize| MARGIN + (threads as f64).log2() / max_log * plot_width;
+
+    let series: [(&str, &str, Vec<f64>); 3] = [
+        (
+            "Allocs/s",
+            "#2563eb",
+            points.iter().map(|p| p.allocations_per_second()).collect(),
+        ),
+        (
+            "Aggregation (ms)",
+            "#dc2626",
+            points
+                .iter()
+                .map(|p| p.aggregation_duration.as_secs_f64() * 1000.0)
+                .collect(),
+        ),
+        (
+            "Report (ms)",
+            "#16a34a",
+            points
+                .iter()
+                .map(|p| p.report_duration.as_secs_f64() * 1000.0)
+                .collect(),
+        ),
+    ];
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{HEIGHT}" font-family="sans-serif" font-size="12">"#
+    );
+    let _ = writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#);
+    let _ = writeln!(
+        svg,
+        r#"<text x="{MARGIN}" y="24" font-size="16">Thread-count scaling (values normalised to each series' maximum)</text>"#
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.1}" y="24" text-anchor="end" fill="gray">seed {seed}</text>"#,
+        WIDTH - MARGIN
+    );
+
+    // Axes
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{MARGIN}" y1="{0}" x2="{1}" y2="{0}" stroke="black"/>"#,
+        HEIGHT - MARGIN,
+        WIDTH - MARGIN
+    );
+    let _ = writeln!(
+        svg,
+        r#"<line x1="{MARGIN}" y1="{MARGIN}" x2="{MARGIN}" y2="{0}" stroke="black"/>"#,
+        HEIGHT - MARGIN
+    );
+    for point in points {
+        let x = x_of(point.threads);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{x:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            HEIGHT - MARGIN + 16.0,
+            point.threads
+        );
+    }
+    let _ = writeln!(
+        svg,
+        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle">Threads (log2)</text>"#,
+        WIDTH / 2.0,
+        HEIGHT - 12.0
+    );
+
+    // Core count marker
+    if cores <= MAX_THREADS {
+        let x = x_of(cores);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x:.1}" y1="{MARGIN}" x2="{x:.1}" y2="{:.1}" stroke="gray" stroke-dasharray="4 4"/>"#,
+            HEIGHT - MARGIN
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" fill="gray">{cores} cores</text>"#,
+            x + 4.0,
+            MARGIN + 12.0
+        );
+    }
+
+    // Series, each normalised to its own maximum so they share the y-axis
+    for (idx, (label, color, values)) in series.iter().enumerate() {
+        let max = values.iter().cloned().fold(0.0f64, f64::max).max(1e-9);
+        let coords: Vec<String> = points
+            .iter()
+            .zip(values)
+            .map(|(point, value)| {
+                let y = HEIGHT - MARGIN - value / max * plot_height;
+                format!("{:.1},{:.1}", x_of(point.threads), y)
+            })
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<polyline fill="none" stroke="{color}" stroke-width="2" points="{}"/>"#,
+            coords.join(" ")
+        );
+
+        let legend_y = MARGIN + 16.0 * idx as f64;
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="12" height="12" fill="{color}"/>"#,
+            WIDTH - MARGIN - 200.0,
+            legend_y
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}">{label} (max {max:.2})</text>"#,
+            WIDTH - MARGIN - 182.0,
+            legend_y + 10.0
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -0,0 +1,1108 @@
+//! Parser for Rust type strings as they appear in `AllocationInfo::type_name`
+//!
+//! Covers paths with nested generic arguments (`std::collections::HashMap<K,
+//! Arc<Mutex<V>>>`), associated type bindings (`Future<Output = T>`), `Fn`
+//! sugar, `dyn`/`impl` bounds (`dyn Handler + Send + 'static`), references,
+//! raw pointers, tuples, arrays, slices, function pointers, `!`, `_` and
+//! qualified paths (`<T as Trait>::Item`). The tree renders back to a
+//! canonical string, so `Vec<  u8 >` and `Vec<u8>` compare equal.
+
+use std::fmt;
+
+/// A parsed type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeTree {
+    /// `std::sync::Arc<T>`, `Fn(u8) -> bool`
+    Path(TypePath),
+    /// `<Vec<u8> as IntoIterator>::Item`
+    QualifiedPath {
+        self_type: Box<TypeTree>,
+        as_trait: Option<TypePath>,
+        segments: Vec<PathSegment>,
+    },
+    /// `&'a mut T`
+    Reference {
+        lifetime: Option<String>,
+        mutable: bool,
+        referent: Box<TypeTree>,
+    },
+    /// `*const T`, `*mut T`
+    RawPointer {
+        mutable: bool,
+        pointee: Box<TypeTree>,
+    },
+    /// `dyn Trait + Send`
+    TraitObject(Vec<Bound>),
+    /// `impl Trait + Send`
+    ImplTrait(Vec<Bound>),
+    /// `()`, `(A,)`, `(A, B)`
+    Tuple(Vec<TypeTree>),
+    /// `[T; N]`; the length is kept as written
+    Array { element: Box<TypeTree>, len: String },
+    /// `[T]`
+    Slice(Box<TypeTree>),
+    /// `fn(A) -> B`, `unsafe fn()`
+    FnPointer {
+        is_unsafe: bool,
+        inputs: Vec<TypeTree>,
+        output: Option<Box<TypeTree>>,
+    },
+    /// `!`
+    Never,
+    /// `_`
+    Inferred,
+}
+
+/// Segments of a path, optionally starting at the crate root (`::std::..`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypePath {
+    pub global: bool,
+    pub segments: Vec<PathSegment>,
+}
+
+/// One `::`-separated segment with its generic arguments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathSegment {
+    pub name: String,
+    pub args: Vec<GenericArg>,
+    /// `Fn(A, B) -> C` sugar: inputs are `Type` args, the return type an
+    /// `Output` binding
+    pub parenthesized: bool,
+}
+
+/// An argument between `<` and `>`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenericArg {
+    Lifetime(String),
+    Type(TypeTree),
+    /// `Output = T`
+    Binding {
+        name: String,
+        value: TypeTree,
+    },
+    /// `3`, `{ N + 1 }`
+    Const(String),
+}
+
+/// One `+`-separated bound of a `dyn` or `impl` type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Bound {
+    Trait {
+        /// `for<'a>` lifetimes
+        higher_ranked: Vec<String>,
+        /// `?Sized`
+        maybe: bool,
+        path: TypePath,
+    },
+    Lifetime(String),
+}
+
+impl TypeTree {
+    /// Parse a complete type string
+    pub fn parse(input: &str) -> Result<TypeTree, String> {
+        let mut parser = Parser {
+            input,
+            tokens: tokenize(input)?,
+            pos: 0,
+        };
+        let tree = parser.parse_type()?;
+        match parser.peek() {
+            None => Ok(tree),
+            Some(_) => Err(parser.error("end of type")),
+        }
+    }
+
+    /// The type without the generic arguments of its last path segment, e.g.
+    /// `std::sync::Arc` for `std::sync::Arc<T>`; other kinds render whole
+    pub fn base_type(&self) -> String {
+        match self {
+            TypeTree::Path(path) => {
+                let names: Vec<&str> = path.segments.iter().map(|s| s.name.as_str()).collect();
+                format!(
+                    "{}{}",
+                    if path.global { "::" } else { "" },
+                    names.join("::")
+                )
+            }
+            other => other.to_string(),
+        }
+    }
+
+    /// Generic arguments of the outermost path other than associated type
+    /// bindings, rendered canonically
+    pub fn type_arguments(&self) -> Vec<String> {
+        match self.last_segment() {
+            Some(segment) if !segment.parenthesized => segment
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Lifetime(lifetime) => Some(lifetime.clone()),
+                    GenericArg::Type(tree) => Some(tree.to_string()),
+                    GenericArg::Const(value) => Some(value.clone()),
+                    GenericArg::Binding { .. } => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Generic types the value is nested in, outermost first: the outermost
+    /// generic path, then through each one holding a single type argument,
+    /// e.g. `Pin`, `Box` for `Pin<Box<dyn Future<Output = ()>>>`
+    pub fn wrapper_chain(&self) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut current = self;
+        while let Some(segment) = current.last_segment() {
+            if segment.parenthesized {
+                break;
+            }
+            let types: Vec<&TypeTree> = segment
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type(tree) => Some(tree),
+                    _ => None,
+                })
+                .collect();
+            if types.is_empty() {
+                break;
+            }
+            chain.push(segment.name.clone());
+            match types.as_slice() {
+                [inner] => current = inner,
+                _ => break,
+            }
+        }
+        chain
+    }
+
+    /// Names of associated type bindings anywhere in the type, first
+    /// occurrence first, e.g. `Output` for `Box<dyn Fn() -> u8>`
+    pub fn associated_type_names(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        self.for_each_segment(&mut |segment| {
+            for arg in &segment.args {
+                if let GenericArg::Binding { name, .. } = arg {
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+        });
+        names
+    }
+
+    /// First type, outside in and left to right, that `predicate` accepts,
+    /// e.g. the `dyn Future<Output = ()>` of `Pin<Box<dyn Future<Output = ()>>>`
+    pub fn find(&self, predicate: &dyn Fn(&TypeTree) -> bool) -> Option<&TypeTree> {
+        if predicate(self) {
+            return Some(self);
+        }
+        self.children()
+            .into_iter()
+            .find_map(|child| child.find(predicate))
+    }
+
+    /// Types directly nested in this one, left to right
+    fn children(&self) -> Vec<&TypeTree> {
+        fn segment_types<'a>(segments: &'a [PathSegment], children: &mut Vec<&'a TypeTree>) {
+            for segment in segments {
+                for arg in &segment.args {
+                    if let GenericArg::Type(tree) | GenericArg::Binding { value: tree, .. } = arg {
+                        children.push(tree);
+                    }
+                }
+            }
+        }
+
+        let mut children = Vec::new();
+        match self {
+            TypeTree::Path(path) => segment_types(&path.segments, &mut children),
+            TypeTree::QualifiedPath {
+                self_type,
+                as_trait,
+                segments,
+            } => {
+                children.push(self_type.as_ref());
+                if let Some(path) = as_trait {
+                    segment_types(&path.segments, &mut children);
+                }
+                segment_types(segments, &mut children);
+            }
+            TypeTree::Reference {
+                referent: inner, ..
+            }
+            | TypeTree::RawPointer { pointee: inner, .. }
+            | TypeTree::Array { element: inner, .. }
+            | TypeTree::Slice(inner) => children.push(inner.as_ref()),
+            TypeTree::TraitObject(bounds) | TypeTree::ImplTrait(bounds) => {
+                for bound in bounds {
+                    if let Bound::Trait { path, .. } = bound {
+                        segment_types(&path.segments, &mut children);
+                    }
+                }
+            }
+            TypeTree::Tuple(elements) => children.extend(elements),
+            TypeTree::FnPointer { inputs, output, .. } => {
+                children.extend(inputs);
+                children.extend(output.as_deref());
+            }
+            TypeTree::Never | TypeTree::Inferred => {}
+        }
+        children
+    }
+
+    fn last_segment(&self) -> Option<&PathSegment> {
+        match self {
+            TypeTree::Path(path) => path.segments.last(),
+            _ => None,
+        }
+    }
+
+    /// Call `visit` on every path segment, outside in
+    fn for_each_segment(&self, visit: &mut dyn FnMut(&PathSegment)) {
+        fn path_segments(path: &TypePath, visit: &mut dyn FnMut(&PathSegment)) {
+            for segment in &path.segments {
+                segment_tree(segment, visit);
+            }
+        }
+        fn segment_tree(segment: &PathSegment, visit: &mut dyn FnMut(&PathSegment)) {
+            visit(segment);
+            for arg in &segment.args {
+                match arg {
+                    GenericArg::Type(tree) | GenericArg::Binding { value: tree, .. } => {
+                        tree.for_each_segment(visit)
+                    }
+                    GenericArg::Lifetime(_) | GenericArg::Const(_) => {}
+                }
+            }
+        }
+
+        match self {
+            TypeTree::Path(path) => path_segments(path, visit),
+            TypeTree::QualifiedPath {
+                self_type,
+                as_trait,
+                segments,
+            } => {
+                self_type.for_each_segment(visit);
+                if let Some(path) = as_trait {
+                    path_segments(path, visit);
+                }
+                for segment in segments {
+                    segment_tree(segment, visit);
+                }
+            }
+            TypeTree::Reference {
+                referent: inner, ..
+            }
+            | TypeTree::RawPointer { pointee: inner, .. }
+            | TypeTree::Array { element: inner, .. }
+            | TypeTree::Slice(inner) => inner.for_each_segment(visit),
+            TypeTree::TraitObject(bounds) | TypeTree::ImplTrait(bounds) => {
+                for bound in bounds {
+                    if let Bound::Trait { path, .. } = bound {
+                        path_segments(path, visit);
+                    }
+                }
+            }
+            TypeTree::Tuple(elements) => {
+                for element in elements {
+                    element.for_each_segment(visit);
+                }
+            }
+            TypeTree::FnPointer { inputs, output, .. } => {
+                for input in inputs.iter().chain(output.as_deref()) {
+                    input.for_each_segment(visit);
+                }
+            }
+            TypeTree::Never | TypeTree::Inferred => {}
+        }
+    }
+}
+
+impl fmt::Display for TypeTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeTree::Path(path) => write!(f, "{}", path),
+            TypeTree::QualifiedPath {
+                self_type,
+                as_trait,
+                segments,
+            } => {
+                write!(f, "<{}", self_type)?;
+                if let Some(path) = as_trait {
+                    write!(f, " as {}", path)?;
+                }
+                write!(f, ">")?;
+                for segment in segments {
+                    write!(f, "::{}", segment)?;
+                }
+                Ok(())
+            }
+            TypeTree::Reference {
+                lifetime,
+                mutable,
+                referent,
+            } => {
+                write!(f, "&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "{} ", lifetime)?;
+                }
+                if *mutable {
+                    write!(f, "mut ")?;
+                }
+                write!(f, "{}", referent)
+            }
+            TypeTree::RawPointer { mutable, pointee } => {
+                let kind = if *mutable { "mut" } else { "const" };
+                write!(f, "*{} {}", kind, pointee)
+            }
+            TypeTree::TraitObject(bounds) => write!(f, "dyn {}", BoundList(bounds)),
+            TypeTree::ImplTrait(bounds) => write!(f, "impl {}", BoundList(bounds)),
+            TypeTree::Tuple(elements) => match elements.as_slice() {
+                [single] => write!(f, "({},)", single),
+                _ => write!(f, "({})", CommaList(elements)),
+            },
+            TypeTree::Array { element, len } => write!(f, "[{}; {}]", element, len),
+            TypeTree::Slice(element) => write!(f, "[{}]", element),
+            TypeTree::FnPointer {
+                is_unsafe,
+                inputs,
+                output,
+            } => {
+                if *is_unsafe {
+                    write!(f, "unsafe ")?;
+                }
+                write!(f, "fn({})", CommaList(inputs))?;
+                if let Some(output) = output {
+                    write!(f, " -> {}", output)?;
+                }
+                Ok(())
+            }
+            TypeTree::Never => write!(f, "!"),
+            TypeTree::Inferred => write!(f, "_"),
+        }
+    }
+}
+
+impl fmt::Display for TypePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.global {
+            write!(f, "::")?;
+        }
+        for (idx, segment) in self.segments.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "::")?;
+            }
+            write!(f, "{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.parenthesized {
+            let mut output = None;
+            let inputs: Vec<&TypeTree> = self
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArg::Type(tree) => Some(tree),
+                    GenericArg::Binding { value, .. } => {
+                        output = Some(value);
+                        None
+                    }
+                    _ => None,
+                })
+                .collect();
+            write!(f, "({})", CommaList(&inputs))?;
+            if let Some(output) = output {
+                write!(f, " -> {}", output)?;
+            }
+            Ok(())
+        } else if self.args.is_empty() {
+            Ok(())
+        } else {
+            write!(f, "<{}>", CommaList(&self.args))
+        }
+    }
+}
+
+impl fmt::Display for GenericArg {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenericArg::Lifetime(lifetime) => write!(f, "{}", lifetime),
+            GenericArg::Type(tree) => write!(f, "{}", tree),
+            GenericArg::Binding { name, value } => write!(f, "{} = {}", name, value),
+            GenericArg::Const(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Display for Bound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bound::Trait {
+                higher_ranked,
+                maybe,
+                path,
+            } => {
+                if !higher_ranked.is_empty() {
+                    write!(f, "for<{}> ", higher_ranked.join(", "))?;
+                }
+                if *maybe {
+                    write!(f, "?")?;
+                }
+                write!(f, "{}", path)
+            }
+            Bound::Lifetime(lifetime) => write!(f, "{}", lifetime),
+        }
+    }
+}
+
+/// Items joined with `, `
+struct CommaList<'a, T>(&'a [T]);
+
+impl<T: fmt::Display> fmt::Display for CommaList<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, item) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", item)?;
+        }
+        Ok(())
+    }
+}
+
+/// Bounds joined with ` + `
+struct BoundList<'a>(&'a [Bound]);
+
+impl fmt::Display for BoundList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, bound) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{}", bound)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Lifetime(String),
+    Number(String),
+    Punct(&'static str),
+}
+
+/// Punctuation, longest first so `::` and `->` win over their prefixes
+const PUNCTUATION: [&str; 18] = [
+    "::", "->", "<", ">", ",", "(", ")", "[", "]", "{", "}", ";", "&", "*", "+", "=", "!", "?",
+];
+
+/// Split `input` into tokens paired with their byte offsets
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        let word_end = |chars: &mut std::iter::Peekable<std::str::CharIndices>| {
+            while chars
+                .peek()
+                .is_some_and(|&(_, c)| c.is_alphanumeric() || c == '_')
+            {
+                chars.next();
+            }
+            chars.peek().map_or(input.len(), |&(end, _)| end)
+        };
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '\'' {
+            chars.next();
+            let end = word_end(&mut chars);
+            if end == start + 1 {
+                return Err(format!(
+                    "lifetime without a name at byte {} of '{}'",
+                    start, input
+                ));
+            }
+            tokens.push((Token::Lifetime(input[start..end].to_string()), start));
+        } else if c.is_ascii_digit() {
+            let end = word_end(&mut chars);
+            tokens.push((Token::Number(input[start..end].to_string()), start));
+        } else if c.is_alphabetic() || c == '_' {
+            let end = word_end(&mut chars);
+            tokens.push((Token::Ident(input[start..end].to_string()), start));
+        } else if let Some(punct) = PUNCTUATION.iter().find(|p| input[start..].starts_with(**p)) {
+            for _ in 0..punct.len() {
+                chars.next();
+            }
+            tokens.push((Token::Punct(punct), start));
+        } else {
+            return Err(format!(
+                "unexpected '{}' at byte {} of '{}'",
+                c, start, input
+            ));
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser over the tokens of one type string
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset).map(|(token, _)| token)
+    }
+
+    fn at_punct(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(p)) if *p == punct)
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(name)) if name == keyword)
+    }
+
+    fn eat_punct(&mut self, punct: &str) -> bool {
+        let found = self.at_punct(punct);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found = self.at_keyword(keyword);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect_punct(&mut self, punct: &str) -> Result<(), String> {
+        if self.eat_punct(punct) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", punct)))
+        }
+    }
+
+    fn error(&self, expected: &str) -> String {
+        match self.tokens.get(self.pos) {
+            Some((token, offset)) => format!(
+                "expected {} at byte {} of '{}', found {:?}",
+                expected, offset, self.input, token
+            ),
+            None => format!("expected {} at the end of '{}'", expected, self.input),
+        }
+    }
+
+    fn parse_type(&mut self) -> Result<TypeTree, String> {
+        match self.peek() {
+            Some(Token::Punct("&")) => {
+                self.pos += 1;
+                let lifetime = match self.peek() {
+                    Some(Token::Lifetime(lifetime)) => {
+                        let lifetime = lifetime.clone();
+                        self.pos += 1;
+                        Some(lifetime)
+                    }
+                    _ => None,
+                };
+                let mutable = self.eat_keyword("mut");
+                Ok(TypeTree::Reference {
+                    lifetime,
+                    mutable,
+                    referent: Box::new(self.parse_type()?),
+                })
+            }
+            Some(Token::Punct("*")) => {
+                self.pos += 1;
+                let mutable = if self.eat_keyword("mut") {
+                    true
+                } else if self.eat_keyword("const") {
+                    false
+                } else {
+                    return Err(self.error("'const' or 'mut'"));
+                };
+                Ok(TypeTree::RawPointer {
+                    mutable,
+                    pointee: Box::new(self.parse_type()?),
+                })
+            }
+            Some(Token::Punct("(")) => {
+                self.pos += 1;
+                let (elements, trailing_comma) = self.parse_type_list(")")?;
+                match elements.len() {
+                    1 if !trailing_comma => Ok(elements.into_iter().next().expect("one element")),
+                    _ => Ok(TypeTree::Tuple(elements)),
+                }
+            }
+            Some(Token::Punct("[")) => {
+                self.pos += 1;
+                let element = Box::new(self.parse_type()?);
+                if self.eat_punct(";") {
+                    let len = self.raw_until("]")?;
+                    Ok(TypeTree::Array { element, len })
+                } else {
+                    self.expect_punct("]")?;
+                    Ok(TypeTree::Slice(element))
+                }
+            }
+            Some(Token::Punct("!")) => {
+                self.pos += 1;
+                Ok(TypeTree::Never)
+            }
+            Some(Token::Punct("<")) => self.parse_qualified_path(),
+            Some(Token::Ident(name)) => match name.as_str() {
+                "dyn" => {
+                    self.pos += 1;
+                    Ok(TypeTree::TraitObject(self.parse_bounds()?))
+                }
+                "impl" => {
+                    self.pos += 1;
+                    Ok(TypeTree::ImplTrait(self.parse_bounds()?))
+                }
+                "fn" | "unsafe" => self.parse_fn_pointer(),
+                "_" => {
+                    self.pos += 1;
+                    Ok(TypeTree::Inferred)
+                }
+                _ => Ok(TypeTree::Path(self.parse_path()?)),
+            },
+            Some(Token::Punct("::")) => Ok(TypeTree::Path(self.parse_path()?)),
+            _ => Err(self.error("a type")),
+        }
+    }
+
+    /// Types separated by commas up to `close`; reports a trailing comma
+    fn parse_type_list(&mut self, close: &str) -> Result<(Vec<TypeTree>, bool), String> {
+        let mut types = Vec::new();
+        let mut trailing_comma = false;
+        while !self.eat_punct(close) {
+            types.push(self.parse_type()?);
+            trailing_comma = self.eat_punct(",");
+            if !trailing_comma {
+                self.expect_punct(close)?;
+                break;
+            }
+        }
+        Ok((types, trailing_comma))
+    }
+
+    /// Source text up to the matching `close`, for array lengths and const
+    /// arguments
+    fn raw_until(&mut self, close: &str) -> Result<String, String> {
+        let start = self.tokens.get(self.pos).map(|(_, offset)| *offset);
+        let mut depth = 0usize;
+        loop {
+            match self.tokens.get(self.pos) {
+                None => return Err(self.error(&format!("'{}'", close))),
+                Some((Token::Punct(p), offset)) if *p == close && depth == 0 => {
+                    let text = self.input[start.unwrap_or(*offset)..*offset]
+                        .trim()
+                        .to_string();
+                    if text.is_empty() {
+                        return Err(self.error("an expression"));
+                    }
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some((Token::Punct("[" | "(" | "{"), _)) => depth += 1,
+                Some((Token::Punct("]" | ")" | "}"), _)) => {
+                    depth = depth
+                        .checked_sub(1)
+                        .ok_or_else(|| self.error("an expression"))?
+                }
+                Some(_) => {}
+            }
+            self.pos += 1;
+        }
+    }
+
+    fn parse_fn_pointer(&mut self) -> Result<TypeTree, String> {
+        let is_unsafe = self.eat_keyword("unsafe");
+        if !self.eat_keyword("fn") {
+            return Err(self.error("'fn'"));
+        }
+        self.expect_punct("(")?;
+        let (inputs, _) = self.parse_type_list(")")?;
+        let output = if self.eat_punct("->") {
+            Some(Box::new(self.parse_type()?))
+        } else {
+            None
+        };
+        Ok(TypeTree::FnPointer {
+            is_unsafe,
+            inputs,
+            output,
+        })
+    }
+
+    fn parse_qualified_path(&mut self) -> Result<TypeTree, String> {
+        self.expect_punct("<")?;
+        let self_type = Box::new(self.parse_type()?);
+        let as_trait = if self.eat_keyword("as") {
+            Some(self.parse_path()?)
+        } else {
+            None
+        };
+        self.expect_punct(">")?;
+        let mut segments = Vec::new();
+        while self.eat_punct("::") {
+            segments.push(self.parse_segment()?);
+        }
+        if segments.is_empty() {
+            return Err(self.error("'::' after a qualified self type"));
+        }
+        Ok(TypeTree::QualifiedPath {
+            self_type,
+            as_trait,
+            segments,
+        })
+    }
+
+    fn parse_path(&mut self) -> Result<TypePath, String> {
+        let global = self.eat_punct("::");
+        let mut segments = vec![self.parse_segment()?];
+        while self.at_punct("::") && matches!(self.peek_at(1), Some(Token::Ident(_))) {
+            self.pos += 1;
+            segments.push(self.parse_segment()?);
+        }
+        Ok(TypePath { global, segments })
+    }
+
+    fn parse_segment(&mut self) -> Result<PathSegment, String> {
+        let Some(Token::Ident(name)) = self.peek().cloned() else {
+            return Err(self.error("a path segment"));
+        };
+        self.pos += 1;
+        // Turbofish is legal in type position too: `Vec::<u8>`
+        if self.at_punct("::") && matches!(self.peek_at(1), Some(Token::Punct("<"))) {
+            self.pos += 1;
+        }
+        if self.eat_punct("<") {
+            return Ok(PathSegment {
+                name,
+                args: self.parse_generic_args()?,
+                parenthesized: false,
+            });
+        }
+        if self.eat_punct("(") {
+            let (inputs, _) = self.parse_type_list(")")?;
+            let mut args: Vec<GenericArg> = inputs.into_iter().map(GenericArg::Type).collect();
+            if self.eat_punct("->") {
+                args.push(GenericArg::Binding {
+                    name: "Output".to_string(),
+                    value: self.parse_type()?,
+                });
+            }
+            return Ok(PathSegment {
+                name,
+                args,
+                parenthesized: true,
+            });
+        }
+        Ok(PathSegment {
+            name,
+            args: Vec::new(),
+            parenthesized: false,
+        })
+    }
+
+    /// Arguments after `<` up to and including the matching `>`
+    fn parse_generic_args(&mut self) -> Result<Vec<GenericArg>, String> {
+        let mut args = Vec::new();
+        while !self.eat_punct(">") {
+            let arg = match (self.peek().cloned(), self.peek_at(1)) {
+                (Some(Token::Lifetime(lifetime)), _) => {
+                    self.pos += 1;
+                    GenericArg::Lifetime(lifetime)
+                }
+                (Some(Token::Ident(name)), Some(Token::Punct("="))) => {
+                    self.pos += 2;
+                    GenericArg::Binding {
+                        name,
+                        value: self.parse_type()?,
+                    }
+                }
+                (Some(Token::Number(value)), _) => {
+                    self.pos += 1;
+                    GenericArg::Const(value)
+                }
+                (Some(Token::Punct("{")), _) => {
+                    self.pos += 1;
+                    GenericArg::Const(format!("{{ {} }}", self.raw_until("}")?))
+                }
+                _ => GenericArg::Type(self.parse_type()?),
+            };
+            args.push(arg);
+            if !self.eat_punct(",") {
+                self.expect_punct(">")?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+
+    fn parse_bounds(&mut self) -> Result<Vec<Bound>, String> {
+        let mut bounds = Vec::new();
+        loop {
+            if let Some(Token::Lifetime(lifetime)) = self.peek() {
+                bounds.push(Bound::Lifetime(lifetime.clone()));
+                self.pos += 1;
+            } else if self.eat_punct("(") {
+                // `dyn (Trait) + Send`
+                let mut inner = self.parse_bounds()?;
+                self.expect_punct(")")?;
+                bounds.append(&mut inner);
+            } else {
+                let mut higher_ranked = Vec::new();
+                if self.eat_keyword("for") {
+                    self.expect_punct("<")?;
+                    while let Some(Token::Lifetime(lifetime)) = self.peek() {
+                        higher_ranked.push(lifetime.clone());
+                        self.pos += 1;
+                        if !self.eat_punct(",") {
+                            break;
+                        }
+                    }
+                    self.expect_punct(">")?;
+                }
+                let maybe = self.eat_punct("?");
+                bounds.push(Bound::Trait {
+                    higher_ranked,
+                    maybe,
+                    path: self.parse_path()?,
+                });
+            }
+            if !self.eat_punct("+") {
+                return Ok(bounds);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Single-segment path type without arguments, e.g. `u8`
+    fn simple(name: &str) -> TypeTree {
+        TypeTree::Path(TypePath {
+            global: false,
+            segments: vec![PathSegment {
+                name: name.to_string(),
+                args: Vec::new(),
+                parenthesized: false,
+            }],
+        })
+    }
+
+    fn parse(input: &str) -> TypeTree {
+        TypeTree::parse(input).unwrap_or_else(|e| panic!("{input}: {e}"))
+    }
+
+    #[test]
+    fn nested_map_of_vectors() {
+        let tree = parse("BTreeMap<UserId, Vec<Message<JsonValue>>>");
+        assert_eq!(tree.base_type(), "BTreeMap");
+        assert_eq!(tree.type_arguments(), ["UserId", "Vec<Message<JsonValue>>"]);
+        assert_eq!(tree.wrapper_chain(), ["BTreeMap"]);
+        assert!(tree.associated_type_names().is_empty());
+    }
+
+    #[test]
+    fn shared_cache_map() {
+        let tree = parse("HashMap<String, Arc<Mutex<CacheEntry>>>");
+        assert_eq!(tree.base_type(), "HashMap");
+        assert_eq!(tree.type_arguments(), ["String", "Arc<Mutex<CacheEntry>>"]);
+        let value = match &tree {
+            TypeTree::Path(path) => match &path.segments[0].args[1] {
+                GenericArg::Type(value) => value,
+                other => panic!("unexpected argument {other:?}"),
+            },
+            other => panic!("unexpected tree {other:?}"),
+        };
+        assert_eq!(value.wrapper_chain(), ["Arc", "Mutex"]);
+    }
+
+    #[test]
+    fn pinned_boxed_future() {
+        let tree = parse("Pin<Box<dyn Future<Output = ()>>>");
+        assert_eq!(tree.wrapper_chain(), ["Pin", "Box"]);
+        assert_eq!(tree.associated_type_names(), ["Output"]);
+        let Some(TypeTree::TraitObject(bounds)) =
+            tree.find(&|tree| matches!(tree, TypeTree::TraitObject(_)))
+        else {
+            panic!("no trait object in {tree}");
+        };
+        let [Bound::Trait { path, .. }] = bounds.as_slice() else {
+            panic!("unexpected bounds {bounds:?}");
+        };
+        assert_eq!(path.segments[0].name, "Future");
+        assert_eq!(
+            path.segments[0].args,
+            [GenericArg::Binding {
+                name: "Output".to_string(),
+                value: TypeTree::Tuple(Vec::new()),
+            }]
+        );
+    }
+
+    #[test]
+    fn trait_object_with_auto_trait_and_lifetime() {
+        let tree = parse("dyn Handler + Send + 'static");
+        let TypeTree::TraitObject(bounds) = &tree else {
+            panic!("unexpected tree {tree:?}");
+        };
+        let names: Vec<String> = bounds.iter().map(ToString::to_string).collect();
+        assert_eq!(names, ["Handler", "Send", "'static"]);
+        assert!(matches!(&bounds[2], Bound::Lifetime(lifetime) if lifetime == "'static"));
+    }
+
+    #[test]
+    fn raw_pointer() {
+        assert_eq!(
+            parse("*const T"),
+            TypeTree::RawPointer {
+                mutable: false,
+                pointee: Box::new(simple("T")),
+            }
+        );
+        assert_eq!(
+            parse("*mut T"),
+            TypeTree::RawPointer {
+                mutable: true,
+                pointee: Box::new(simple("T")),
+            }
+        );
+    }
+
+    #[test]
+    fn one_element_tuple() {
+        assert_eq!(parse("(A,)"), TypeTree::Tuple(vec![simple("A")]));
+        assert_eq!(parse("(A,)").to_string(), "(A,)");
+        assert_ne!(parse("(A,)"), parse("(A)"));
+    }
+
+    #[test]
+    fn array_keeps_length_as_written() {
+        assert_eq!(
+            parse("[u8; 4]"),
+            TypeTree::Array {
+                element: Box::new(simple("u8")),
+                len: "4".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn qualified_associated_type() {
+        assert_eq!(
+            parse("<T as Trait>::Item"),
+            TypeTree::QualifiedPath {
+                self_type: Box::new(simple("T")),
+                as_trait: Some(TypePath {
+                    global: false,
+                    segments: vec![PathSegment {
+                        name: "Trait".to_string(),
+                        args: Vec::new(),
+                        parenthesized: false,
+                    }],
+                }),
+                segments: vec![PathSegment {
+                    name: "Item".to_string(),
+                    args: Vec::new(),
+                    parenthesized: false,
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn fn_trait_sugar() {
+        let tree = parse("Fn(u8) -> bool");
+        let TypeTree::Path(path) = &tree else {
+            panic!("unexpected tree {tree:?}");
+        };
+        let segment = &path.segments[0];
+        assert!(segment.parenthesized);
+        assert_eq!(
+            segment.args,
+            [
+                GenericArg::Type(simple("u8")),
+                GenericArg::Binding {
+                    name: "Output".to_string(),
+                    value: simple("bool"),
+                },
+            ]
+        );
+        assert!(tree.type_arguments().is_empty());
+        assert_eq!(tree.associated_type_names(), ["Output"]);
+    }
+
+    #[test]
+    fn display_round_trips() {
+        for input in [
+            "BTreeMap<UserId, Vec<Message<JsonValue>>>",
+            "HashMap<String, Arc<Mutex<CacheEntry>>>",
+            "Pin<Box<dyn Future<Output = ()>>>",
+            "dyn Handler + Send + 'static",
+            "*const T",
+            "*mut T",
+            "(A,)",
+            "(A, B)",
+            "[u8; 4]",
+            "[u8]",
+            "<T as Trait>::Item",
+            "Fn(u8) -> bool",
+            "&'a mut Vec<u8>",
+            "::std::sync::Arc<T>",
+            "unsafe fn(u8) -> !",
+            "impl Iterator<Item = u8>",
+        ] {
+            let tree = parse(input);
+            assert_eq!(tree.to_string(), input);
+            assert_eq!(parse(&tree.to_string()), tree);
+        }
+    }
+
+    #[test]
+    fn display_is_canonical() {
+        assert_eq!(parse("Vec<  u8 >").to_string(), "Vec<u8>");
+        assert_eq!(parse("HashMap<K,V>").to_string(), "HashMap<K, V>");
+        assert_eq!(parse("Vec<  u8 >"), parse("Vec<u8>"));
+    }
+
+    #[test]
+    fn rejects_malformed_types() {
+        for input in [
+            "",
+            "Vec<u8",
+            "Vec<u8>>",
+            "HashMap<String,",
+            "[u8; 4",
+            "*T",
+            "<T as Trait>",
+            "Fn(u8) ->",
+            "Vec<u8> extra",
+        ] {
+            assert!(TypeTree::parse(input).is_err(), "{input:?} parsed");
+        }
+    }
+}